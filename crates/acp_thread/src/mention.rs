@@ -1,5 +1,5 @@
 use agent_client_protocol::schema::v1 as acp;
-use anyhow::{Context as _, Result, bail};
+use anyhow::{Context as _, Result, bail, ensure};
 use file_icons::FileIcons;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -20,6 +20,12 @@ use util::{
 pub enum MentionUri {
     File {
         abs_path: PathBuf,
+        /// A single 0-based line to jump to, emitted as `#L<line + 1>`.
+        /// Unlike [`MentionUri::Selection`], this doesn't refer to a range of content:
+        /// the whole file is attached as context, and the line only positions the
+        /// cursor when the mention is opened.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        line: Option<u32>,
     },
     PastedImage {
         name: String,
@@ -69,6 +75,12 @@ pub enum MentionUri {
     MergeConflict {
         file_path: String,
     },
+    /// A file's contents at a git revision (a commit SHA, branch, or tag),
+    /// written as `file:///path/to/file?rev=<rev>`.
+    GitBlob {
+        abs_path: PathBuf,
+        rev: String,
+    },
     Skill {
         name: String,
         source: String,
@@ -103,6 +115,10 @@ impl MentionUri {
         let path = url.path();
         match url.scheme() {
             "file" => {
+                // `Url` already normalizes `file://localhost/` to an empty host.
+                if url.host_str().is_some() {
+                    bail!("File mention URI must not have a host: {input}");
+                }
                 let trimmed = if path_style.is_windows() {
                     path.trim_start_matches("/")
                 } else {
@@ -119,9 +135,33 @@ impl MentionUri {
                 };
                 let path = normalized.as_ref();
 
-                if let Some(fragment) = url.fragment() {
+                if let Some(rev) = query_param(&url, "rev") {
+                    validate_query_params(&url, &["rev"])?;
+                    ensure!(
+                        url.fragment().is_none(),
+                        "Git blob mention URI must not have a fragment: {input}"
+                    );
+                    ensure!(
+                        git::is_plain_revision(&rev),
+                        "Git blob mention URI has an invalid revision: {input}"
+                    );
+                    Ok(Self::GitBlob {
+                        abs_path: path.into(),
+                        rev,
+                    })
+                } else if let Some(fragment) = url.fragment()
+                    && url.query().is_none()
+                    && let Some(line) = parse_single_line(fragment)
+                {
+                    Ok(Self::File {
+                        abs_path: path.into(),
+                        line: Some(line),
+                    })
+                } else if let Some(fragment) = url.fragment() {
                     validate_query_params(&url, &["symbol", "column"])?;
-                    let line_range = parse_line_range(fragment).log_err().unwrap_or(1..=1);
+                    let line_range = parse_unordered_line_range(fragment)
+                        .log_err()
+                        .map_or(Ok(1..=1), ordered_line_range)?;
                     let column = parse_column(query_param(&url, "column"));
                     if let Some(name) = query_param(&url, "symbol") {
                         Ok(Self::Symbol {
@@ -143,6 +183,7 @@ impl MentionUri {
                 } else {
                     Ok(Self::File {
                         abs_path: path.into(),
+                        line: None,
                     })
                 }
             }
@@ -208,6 +249,7 @@ impl MentionUri {
                         single_query_param(&url, "path")?.context("Missing path for file")?;
                     Ok(Self::File {
                         abs_path: path.into(),
+                        line: None,
                     })
                 } else if path.starts_with("/agent/directory") {
                     let path =
@@ -314,9 +356,10 @@ impl MentionUri {
     /// The absolute path this mention refers to, if it refers to one.
     pub fn abs_path(&self) -> Option<&Path> {
         match self {
-            MentionUri::File { abs_path }
+            MentionUri::File { abs_path, .. }
             | MentionUri::Directory { abs_path }
-            | MentionUri::Symbol { abs_path, .. } => Some(abs_path),
+            | MentionUri::Symbol { abs_path, .. }
+            | MentionUri::GitBlob { abs_path, .. } => Some(abs_path),
             MentionUri::Selection { abs_path, .. } => abs_path.as_deref(),
             MentionUri::Skill {
                 skill_file_path, ..
@@ -334,6 +377,14 @@ impl MentionUri {
 
     pub fn name(&self) -> String {
         match self {
+            MentionUri::File {
+                abs_path,
+                line: Some(line),
+            } => format!(
+                "{} ({})",
+                abs_path.file_name().unwrap_or_default().display(),
+                line + 1
+            ),
             MentionUri::File { abs_path, .. } | MentionUri::Directory { abs_path, .. } => abs_path
                 .file_name()
                 .unwrap_or_default()
@@ -359,6 +410,11 @@ impl MentionUri {
                     .to_string_lossy();
                 format!("Merge Conflict ({name})")
             }
+            MentionUri::GitBlob { abs_path, rev } => format!(
+                "{} @ {}",
+                abs_path.file_name().unwrap_or_default().display(),
+                short_rev(rev)
+            ),
             MentionUri::Selection {
                 abs_path: path,
                 line_range,
@@ -397,7 +453,11 @@ impl MentionUri {
 
     pub fn tooltip_text(&self) -> Option<SharedString> {
         match self {
-            MentionUri::File { abs_path } | MentionUri::Directory { abs_path } => {
+            MentionUri::File {
+                abs_path,
+                line: Some(line),
+            } => Some(format!("{}:{}", abs_path.display(), line + 1).into()),
+            MentionUri::File { abs_path, .. } | MentionUri::Directory { abs_path } => {
                 Some(abs_path.to_string_lossy().into_owned().into())
             }
             MentionUri::Symbol {
@@ -426,6 +486,9 @@ impl MentionUri {
                 )
                 .into(),
             ),
+            MentionUri::GitBlob { abs_path, rev } => {
+                Some(format!("{} @ {}", abs_path.display(), rev).into())
+            }
             MentionUri::Skill {
                 skill_file_path, ..
             } => Some(skill_file_path.to_string_lossy().into_owned().into()),
@@ -435,7 +498,7 @@ impl MentionUri {
 
     pub fn icon_path(&self, cx: &mut App) -> SharedString {
         match self {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 FileIcons::get_icon(abs_path, cx).unwrap_or_else(|| IconName::File.path().into())
             }
             MentionUri::PastedImage { .. } => IconName::Image.path().into(),
@@ -450,6 +513,7 @@ impl MentionUri {
             MentionUri::Fetch { .. } => IconName::ToolWeb.path().into(),
             MentionUri::GitDiff { .. } => IconName::GitBranch.path().into(),
             MentionUri::MergeConflict { .. } => IconName::GitMergeConflict.path().into(),
+            MentionUri::GitBlob { .. } => IconName::GitCommit.path().into(),
             MentionUri::Skill { .. } => IconName::Sparkle.path().into(),
         }
     }
@@ -458,11 +522,42 @@ impl MentionUri {
         MentionLink(self)
     }
 
+    /// Like [`Self::as_link`], but labels file and directory mentions with their
+    /// path relative to `root`, so same-named files in a thread stay distinguishable.
+    /// The link target is still the absolute URI.
+    pub fn to_link_relative(&self, root: &Path) -> String {
+        let relative_label = match self {
+            MentionUri::File { abs_path, line } => {
+                abs_path
+                    .strip_prefix(root)
+                    .ok()
+                    .map(|relative_path| match line {
+                        Some(line) => format!("{} ({})", relative_path.display(), line + 1),
+                        None => relative_path.display().to_string(),
+                    })
+            }
+            MentionUri::Directory { abs_path } => abs_path
+                .strip_prefix(root)
+                .ok()
+                .map(|relative_path| relative_path.display().to_string()),
+            _ => None,
+        };
+        let label = relative_label
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| self.name());
+        let mut link = String::new();
+        write_mention_link(&mut link, &label, &self.to_uri()).ok();
+        link
+    }
+
     pub fn to_uri(&self) -> Url {
         match self {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, line } => {
                 let mut url = Url::parse("file:///").unwrap();
                 url.set_path(&abs_path.to_string_lossy());
+                if let Some(line) = line {
+                    url.set_fragment(Some(&format!("L{}", line + 1)));
+                }
                 url
             }
             MentionUri::PastedImage { name } => {
@@ -569,6 +664,12 @@ impl MentionUri {
                 url.query_pairs_mut().append_pair("path", file_path);
                 url
             }
+            MentionUri::GitBlob { abs_path, rev } => {
+                let mut url = Url::parse("file:///").unwrap();
+                url.set_path(&abs_path.to_string_lossy());
+                url.query_pairs_mut().append_pair("rev", rev);
+                url
+            }
             MentionUri::Skill {
                 name,
                 source,
@@ -590,7 +691,32 @@ pub struct MentionLink<'a>(&'a MentionUri);
 
 impl fmt::Display for MentionLink<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[@{}]({})", self.0.name(), self.0.to_uri())
+        write_mention_link(f, &self.0.name(), &self.0.to_uri())
+    }
+}
+
+/// Writes `[@label](uri)`, escaping the label so names like `a]b.rs` can't end the link text
+/// early, and wrapping the destination in `<...>` when it contains spaces or parentheses.
+fn write_mention_link(out: &mut impl fmt::Write, label: &str, uri: &Url) -> fmt::Result {
+    out.write_str("[@")?;
+    let mut chars = label.chars().peekable();
+    while let Some(char) = chars.next() {
+        let escape = match char {
+            '[' | ']' | '<' | '>' => true,
+            // A lone backslash (as in Windows paths) is literal in CommonMark.
+            '\\' => chars.peek().is_some_and(|next| next.is_ascii_punctuation()),
+            _ => false,
+        };
+        if escape {
+            out.write_char('\\')?;
+        }
+        out.write_char(char)?;
+    }
+    let uri = uri.as_str();
+    if uri.contains([' ', '(', ')']) {
+        write!(out, "](<{uri}>)")
+    } else {
+        write!(out, "]({uri})")
     }
 }
 
@@ -601,6 +727,12 @@ enum DecodePercentEscapes {
 }
 
 fn parse_line_range(fragment: &str) -> Result<RangeInclusive<u32>> {
+    ordered_line_range(parse_unordered_line_range(fragment)?)
+}
+
+/// Parses `L10:20`, `L10-20` and `L10` fragments into a 0-based line range, without checking
+/// that the range runs forwards.
+fn parse_unordered_line_range(fragment: &str) -> Result<RangeInclusive<u32>> {
     let range = fragment.strip_prefix("L").unwrap_or(fragment);
 
     let (start, end) = if let Some((start, end)) = range.split_once(":") {
@@ -627,6 +759,34 @@ fn parse_line_range(fragment: &str) -> Result<RangeInclusive<u32>> {
     Ok(start_line..=end_line)
 }
 
+fn ordered_line_range(line_range: RangeInclusive<u32>) -> Result<RangeInclusive<u32>> {
+    ensure!(
+        line_range.start() <= line_range.end(),
+        "line range end must be >= start"
+    );
+    Ok(line_range)
+}
+
+/// Abbreviates full commit SHAs the way git does, leaving branch and tag names intact.
+fn short_rev(rev: &str) -> &str {
+    const SHORT_SHA_LEN: usize = 7;
+    if rev.len() > SHORT_SHA_LEN && rev.chars().all(|c| c.is_ascii_hexdigit()) {
+        &rev[..SHORT_SHA_LEN]
+    } else {
+        rev
+    }
+}
+
+/// Parses a fragment naming a single line, like `L42`, into a 0-based line.
+/// Ranges (`L42:42`, `L10-20`) are left to [`parse_line_range`].
+fn parse_single_line(fragment: &str) -> Option<u32> {
+    fragment
+        .strip_prefix('L')?
+        .parse::<u32>()
+        .ok()?
+        .checked_sub(1)
+}
+
 /// Returns the mention target as a bare absolute path (not a URL), with the
 /// backticks agents sometimes add stripped.
 fn bare_path_target(input: &str, path_style: PathStyle) -> Option<&str> {
@@ -659,11 +819,22 @@ fn parse_hyperlink_path(
     absolute_path_mention(&path_input, fragment)
 }
 
+/// Builds the mention for a bare absolute path, reading `#L42` fragments the way
+/// `file://` URIs do. Fragments that aren't line ranges are ignored, but inverted
+/// ranges are rejected.
 fn absolute_path_mention(path_input: &str, fragment: Option<&str>) -> Result<MentionUri> {
-    if let Some(fragment) = fragment.and_then(|fragment| parse_line_range(fragment).ok()) {
+    if let Some(line) = fragment.and_then(parse_single_line) {
+        return Ok(MentionUri::File {
+            abs_path: path_input.into(),
+            line: Some(line),
+        });
+    }
+    if let Some(line_range) =
+        fragment.and_then(|fragment| parse_unordered_line_range(fragment).ok())
+    {
         return Ok(MentionUri::Selection {
             abs_path: Some(path_input.into()),
-            line_range: fragment,
+            line_range: ordered_line_range(line_range)?,
             column: None,
         });
     }
@@ -682,7 +853,10 @@ fn absolute_path_mention(path_input: &str, fragment: Option<&str>) -> Result<Men
                 .map(|column| column.saturating_sub(1)),
         })
     } else {
-        Ok(MentionUri::File { abs_path })
+        Ok(MentionUri::File {
+            abs_path,
+            line: None,
+        })
     }
 }
 
@@ -869,7 +1043,7 @@ mod tests {
         let file_uri = uri!("file:///path/to/file.rs");
         let parsed = MentionUri::parse(file_uri, PathStyle::local()).unwrap();
         match &parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, Path::new(path!("/path/to/file.rs")));
             }
             _ => panic!("Expected File variant"),
@@ -877,6 +1051,87 @@ mod tests {
         assert_eq!(parsed.to_uri().to_string(), file_uri);
     }
 
+    #[test]
+    fn test_to_link_relative_disambiguates_same_named_files() {
+        let root = Path::new(path!("/project"));
+        let foo_a = MentionUri::File {
+            abs_path: PathBuf::from(path!("/project/a/foo.rs")),
+            line: None,
+        };
+        let foo_b = MentionUri::File {
+            abs_path: PathBuf::from(path!("/project/b/foo.rs")),
+            line: None,
+        };
+
+        assert_eq!(foo_a.name(), foo_b.name());
+        assert_eq!(
+            foo_a.to_link_relative(root),
+            format!(
+                "[@{}]({})",
+                Path::new("a").join("foo.rs").display(),
+                foo_a.to_uri()
+            )
+        );
+        assert_eq!(
+            foo_b.to_link_relative(root),
+            format!(
+                "[@{}]({})",
+                Path::new("b").join("foo.rs").display(),
+                foo_b.to_uri()
+            )
+        );
+
+        // Mentions outside the root fall back to the base name.
+        let outside = MentionUri::File {
+            abs_path: PathBuf::from(path!("/elsewhere/foo.rs")),
+            line: None,
+        };
+        assert_eq!(
+            outside.to_link_relative(root),
+            outside.as_link().to_string()
+        );
+    }
+
+    #[test]
+    fn test_link_escapes_label_and_wraps_destination() {
+        let symbol = MentionUri::Symbol {
+            abs_path: PathBuf::from(path!("/path/to/lib.rs")),
+            name: "Index<[T]>".into(),
+            line_range: 0..=0,
+        };
+        assert_eq!(
+            symbol.as_link().to_string(),
+            format!(r"[@Index\<\[T\]\>]({})", symbol.to_uri())
+        );
+
+        let file = MentionUri::File {
+            abs_path: PathBuf::from(path!("/path/with space/f(1).rs")),
+            line: None,
+        };
+        let uri = file.to_uri().to_string();
+        assert!(uri.contains("with%20space"), "{uri}");
+        assert_eq!(file.as_link().to_string(), format!("[@f(1).rs](<{uri}>)"));
+    }
+
+    #[test]
+    fn test_parse_file_uri_host() {
+        assert!(MentionUri::parse("file://somehost/path/to/file.rs", PathStyle::Unix).is_err());
+
+        for uri in [
+            "file:///path/to/file.rs",
+            "file://localhost/path/to/file.rs",
+        ] {
+            assert_eq!(
+                MentionUri::parse(uri, PathStyle::Unix).unwrap(),
+                MentionUri::File {
+                    abs_path: PathBuf::from("/path/to/file.rs"),
+                    line: None,
+                },
+                "{uri}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_directory_uri() {
         let file_uri = uri!("file:///path/to/dir/");
@@ -894,7 +1149,7 @@ mod tests {
     fn test_parse_file_uris_use_native_separators_on_windows() {
         let parsed = MentionUri::parse("file:///C:/path/to/file.rs", PathStyle::Windows).unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, PathBuf::from("C:\\path\\to\\file.rs"));
             }
             other => panic!("Expected File variant, got {other:?}"),
@@ -939,14 +1194,15 @@ mod tests {
             MentionUri::parse("file:///C:/path%20with%20space/file.rs", PathStyle::Windows)
                 .unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, PathBuf::from("C:\\path with space\\file.rs"));
             }
             other => panic!("Expected File variant, got {other:?}"),
         }
         assert_eq!(
             MentionUri::File {
-                abs_path: PathBuf::from("C:\\path with space\\file.rs")
+                abs_path: PathBuf::from("C:\\path with space\\file.rs"),
+                line: None,
             }
             .to_uri()
             .to_string(),
@@ -1008,7 +1264,7 @@ mod tests {
         )
         .unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(
                     abs_path,
                     PathBuf::from("C:\\Projects\\Example Workspace\\AGENTS.md")
@@ -1022,17 +1278,13 @@ mod tests {
     fn test_parse_windows_drive_path_with_leading_slash_and_fragment_line() {
         let parsed =
             MentionUri::parse_hyperlink("/C:/Projects/Cargo.toml#L4", PathStyle::Windows).unwrap();
-        match parsed {
-            MentionUri::Selection {
-                abs_path: Some(abs_path),
-                line_range,
-                ..
-            } => {
-                assert_eq!(abs_path, PathBuf::from("C:\\Projects\\Cargo.toml"));
-                assert_eq!(line_range, 3..=3);
+        assert_eq!(
+            parsed,
+            MentionUri::File {
+                abs_path: PathBuf::from("C:\\Projects\\Cargo.toml"),
+                line: Some(3),
             }
-            other => panic!("Expected Selection variant, got {other:?}"),
-        }
+        );
     }
 
     #[test]
@@ -1041,7 +1293,8 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("C:\\dir\\file.rs")
+                abs_path: PathBuf::from("C:\\dir\\file.rs"),
+                line: None,
             }
         );
         let uri = parsed.to_uri().to_string();
@@ -1054,7 +1307,7 @@ mod tests {
         let parsed =
             MentionUri::parse_hyperlink("//server/share/dir/file.rs", PathStyle::Windows).unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, PathBuf::from("\\\\server\\share\\dir\\file.rs"));
             }
             other => panic!("Expected File variant, got {other:?}"),
@@ -1074,7 +1327,8 @@ mod tests {
             assert_eq!(
                 parsed,
                 MentionUri::File {
-                    abs_path: PathBuf::from("C:\\foo\\bar.rs")
+                    abs_path: PathBuf::from("C:\\foo\\bar.rs"),
+                    line: None,
                 },
                 "input: {input}"
             );
@@ -1086,7 +1340,7 @@ mod tests {
         // Uppercase `/C/foo` is more likely a real directory than a drive.
         let parsed = MentionUri::parse_hyperlink("/C/Users/readme.md", PathStyle::Windows).unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, PathBuf::from("\\C\\Users\\readme.md"));
             }
             other => panic!("Expected File variant, got {other:?}"),
@@ -1097,7 +1351,7 @@ mod tests {
     fn test_posix_paths_are_not_rewritten_as_windows_drives() {
         let parsed = MentionUri::parse_hyperlink("/c/Projects/AGENTS.md", PathStyle::Unix).unwrap();
         match parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, PathBuf::from("/c/Projects/AGENTS.md"));
             }
             other => panic!("Expected File variant, got {other:?}"),
@@ -1110,7 +1364,8 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("/tmp/a b.rs")
+                abs_path: PathBuf::from("/tmp/a b.rs"),
+                line: None,
             }
         );
 
@@ -1120,7 +1375,8 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("C:\\dir\\100%_done.txt")
+                abs_path: PathBuf::from("C:\\dir\\100%_done.txt"),
+                line: None,
             }
         );
 
@@ -1129,14 +1385,16 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("/tmp/a%2Fb.rs")
+                abs_path: PathBuf::from("/tmp/a%2Fb.rs"),
+                line: None,
             }
         );
         let parsed = MentionUri::parse_hyperlink("/tmp/..%2F..%2Fsecret", PathStyle::Unix).unwrap();
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("/tmp/..%2F..%2Fsecret")
+                abs_path: PathBuf::from("/tmp/..%2F..%2Fsecret"),
+                line: None,
             }
         );
     }
@@ -1147,7 +1405,8 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("/tmp/a%20b.rs")
+                abs_path: PathBuf::from("/tmp/a%20b.rs"),
+                line: None,
             }
         );
 
@@ -1155,7 +1414,8 @@ mod tests {
         assert_eq!(
             parsed,
             MentionUri::File {
-                abs_path: PathBuf::from("/c/Projects/AGENTS.md")
+                abs_path: PathBuf::from("/c/Projects/AGENTS.md"),
+                line: None,
             }
         );
     }
@@ -1167,7 +1427,8 @@ mod tests {
         assert_eq!(
             literal,
             MentionUri::File {
-                abs_path: PathBuf::from("/tmp/a%20b.rs")
+                abs_path: PathBuf::from("/tmp/a%20b.rs"),
+                line: None,
             }
         );
 
@@ -1189,7 +1450,8 @@ mod tests {
         assert_eq!(
             literal,
             MentionUri::File {
-                abs_path: PathBuf::from("C:\\dir\\a%20b.rs")
+                abs_path: PathBuf::from("C:\\dir\\a%20b.rs"),
+                line: None,
             }
         );
     }
@@ -1247,6 +1509,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_directory_and_file_mentions_of_same_path_differ() {
+        let directory = MentionUri::Directory {
+            abs_path: PathBuf::from(path!("/path/to/dir")),
+        };
+        let file = MentionUri::File {
+            abs_path: PathBuf::from(path!("/path/to/dir")),
+            line: None,
+        };
+        assert_eq!(directory.name(), "dir");
+        assert_ne!(directory, file);
+        assert_ne!(directory.to_uri(), file.to_uri());
+
+        let parsed_file =
+            MentionUri::parse(&file.to_uri().to_string(), PathStyle::local()).unwrap();
+        assert_eq!(parsed_file, file);
+        let parsed_directory =
+            MentionUri::parse(&directory.to_uri().to_string(), PathStyle::local()).unwrap();
+        assert!(
+            matches!(parsed_directory, MentionUri::Directory { .. }),
+            "expected Directory variant, got {parsed_directory:?}"
+        );
+    }
+
     #[test]
     fn test_parse_symbol_uri() {
         let symbol_uri = uri!("file:///path/to/file.rs?symbol=MySymbol#L10:20");
@@ -1268,6 +1554,26 @@ mod tests {
         assert_eq!(parsed.to_uri().to_string(), symbol_uri);
     }
 
+    #[test]
+    fn test_symbol_and_path_percent_encoding_round_trip() {
+        let symbol = MentionUri::Symbol {
+            abs_path: PathBuf::from(path!("/path/with space/a#b.rs")),
+            name: "operator==".into(),
+            line_range: 0..=0,
+        };
+        let uri = symbol.to_uri().to_string();
+        assert!(uri.contains("symbol=operator%3D%3D"), "{uri}");
+        assert_eq!(MentionUri::parse(&uri, PathStyle::local()).unwrap(), symbol);
+
+        let file = MentionUri::File {
+            abs_path: PathBuf::from(path!("/path/with space/file.rs")),
+            line: None,
+        };
+        let uri = file.to_uri().to_string();
+        assert!(uri.contains("with%20space"), "{uri}");
+        assert_eq!(MentionUri::parse(&uri, PathStyle::local()).unwrap(), file);
+    }
+
     #[test]
     fn test_parse_selection_uri() {
         let selection_uri = uri!("file:///path/to/file.rs#L5:15");
@@ -1287,12 +1593,59 @@ mod tests {
         assert_eq!(parsed.to_uri().to_string(), selection_uri);
     }
 
+    #[test]
+    fn test_parse_rejects_inverted_line_ranges() {
+        for uri in [
+            uri!("file:///path/to/file.rs#L20:10"),
+            uri!("file:///path/to/file.rs?symbol=MySymbol#L20:10"),
+            "zed:///agent/selection?path=%2Fpath%2Fto%2Ffile.rs#L20:10",
+        ] {
+            let error = MentionUri::parse(uri, PathStyle::local()).unwrap_err();
+            assert!(
+                format!("{error:#}").contains("line range end must be >= start"),
+                "unexpected error for {uri}: {error}"
+            );
+        }
+
+        for (fragment, expected) in [("L10:10", 9..=9), ("L10:20", 9..=19), ("section", 1..=1)] {
+            let uri = format!("{}#{fragment}", uri!("file:///path/to/file.rs"));
+            match MentionUri::parse(&uri, PathStyle::local()).unwrap() {
+                MentionUri::Selection { line_range, .. } => assert_eq!(line_range, expected),
+                other => panic!("Expected Selection variant, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_bare_paths_reject_inverted_line_ranges() {
+        let bare = path!("/path/to/file.rs#L20:10");
+        for result in [
+            MentionUri::parse(bare, PathStyle::local()),
+            MentionUri::parse_hyperlink(bare, PathStyle::local()),
+        ] {
+            let error = result.unwrap_err();
+            assert!(
+                format!("{error:#}").contains("line range end must be >= start"),
+                "unexpected error for {bare}: {error:#}"
+            );
+        }
+
+        // Fragments that aren't line ranges still fall back to a plain file.
+        assert_eq!(
+            MentionUri::parse(path!("/path/to/file.rs#section"), PathStyle::local()).unwrap(),
+            MentionUri::File {
+                abs_path: PathBuf::from(path!("/path/to/file.rs")),
+                line: None,
+            }
+        );
+    }
+
     #[test]
     fn test_parse_file_uri_with_non_ascii() {
         let file_uri = uri!("file:///path/to/%E6%97%A5%E6%9C%AC%E8%AA%9E.txt");
         let parsed = MentionUri::parse(file_uri, PathStyle::local()).unwrap();
         match &parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, Path::new(path!("/path/to/日本語.txt")));
             }
             _ => panic!("Expected File variant"),
@@ -1386,6 +1739,42 @@ mod tests {
         assert_eq!(parsed, skill_uri);
     }
 
+    #[test]
+    fn test_git_blob_uri_round_trip() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let blob_uri = MentionUri::GitBlob {
+            abs_path: PathBuf::from(path!("/path/to/my file.rs")),
+            rev: sha.to_string(),
+        };
+
+        let serialized = blob_uri.to_uri().to_string();
+        let parsed = MentionUri::parse(&serialized, PathStyle::local()).unwrap();
+        assert_eq!(parsed, blob_uri);
+        assert_eq!(parsed.name(), "my file.rs @ 0123456");
+        assert_eq!(
+            parsed.abs_path(),
+            Some(Path::new(path!("/path/to/my file.rs")))
+        );
+
+        let branch_uri = MentionUri::GitBlob {
+            abs_path: PathBuf::from(path!("/path/to/lib.rs")),
+            rev: "feature/blob-mentions".to_string(),
+        };
+        let parsed =
+            MentionUri::parse(&branch_uri.to_uri().to_string(), PathStyle::local()).unwrap();
+        assert_eq!(parsed, branch_uri);
+        assert_eq!(parsed.name(), "lib.rs @ feature/blob-mentions");
+
+        assert!(
+            serialized.starts_with("file:///") && serialized.ends_with(&format!("?rev={sha}")),
+            "{serialized}"
+        );
+        assert!(MentionUri::parse("file:///lib.rs?rev=abc123#L4", PathStyle::local()).is_err());
+        assert!(MentionUri::parse("file:///lib.rs?rev=a&symbol=b", PathStyle::local()).is_err());
+        assert!(MentionUri::parse("file:///lib.rs?rev=HEAD%0Aother", PathStyle::local()).is_err());
+        assert!(MentionUri::parse("file:///lib.rs?rev=HEAD:other.rs", PathStyle::local()).is_err());
+    }
+
     #[test]
     fn test_parse_fetch_http_uri() {
         let http_uri = "http://example.com/path?query=value#fragment";
@@ -1464,7 +1853,7 @@ mod tests {
         let file_path = path!("/path/to/file.rs");
         let parsed = MentionUri::parse(file_path, PathStyle::local()).unwrap();
         match &parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, Path::new(file_path));
             }
             _ => panic!("Expected File variant"),
@@ -1516,18 +1905,13 @@ mod tests {
     fn test_parse_absolute_file_path_with_fragment_line() {
         let file_path = "/path/to/file.rs#L42";
         let parsed = MentionUri::parse(file_path, PathStyle::Unix).unwrap();
-        match &parsed {
-            MentionUri::Selection {
-                abs_path: path,
-                line_range,
-                ..
-            } => {
-                assert_eq!(path.as_ref().unwrap(), Path::new("/path/to/file.rs"));
-                assert_eq!(line_range.start(), &41);
-                assert_eq!(line_range.end(), &41);
+        assert_eq!(
+            parsed,
+            MentionUri::File {
+                abs_path: PathBuf::from("/path/to/file.rs"),
+                line: Some(41),
             }
-            _ => panic!("Expected Selection variant"),
-        }
+        );
     }
 
     #[test]
@@ -1535,7 +1919,7 @@ mod tests {
         let file_path = "C:\\Users\\zed\\project\\main.rs";
         let parsed = MentionUri::parse(file_path, PathStyle::Windows).unwrap();
         match &parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, Path::new("C:\\Users\\zed\\project\\main.rs"));
             }
             _ => panic!("Expected File variant"),
@@ -1567,21 +1951,13 @@ mod tests {
     fn test_parse_absolute_windows_file_path_with_fragment_line() {
         let file_path = "C:\\Users\\zed\\project\\main.rs#L42";
         let parsed = MentionUri::parse(file_path, PathStyle::Windows).unwrap();
-        match &parsed {
-            MentionUri::Selection {
-                abs_path: path,
-                line_range,
-                ..
-            } => {
-                assert_eq!(
-                    path.as_ref().unwrap(),
-                    Path::new("C:\\Users\\zed\\project\\main.rs")
-                );
-                assert_eq!(line_range.start(), &41);
-                assert_eq!(line_range.end(), &41);
+        assert_eq!(
+            parsed,
+            MentionUri::File {
+                abs_path: PathBuf::from("C:\\Users\\zed\\project\\main.rs"),
+                line: Some(41),
             }
-            _ => panic!("Expected Selection variant"),
-        }
+        );
     }
 
     #[test]
@@ -1589,7 +1965,7 @@ mod tests {
         let file_path = "`/path/to/file.rs`";
         let parsed = MentionUri::parse(file_path, PathStyle::Unix).unwrap();
         match &parsed {
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 assert_eq!(abs_path, Path::new("/path/to/file.rs"));
             }
             _ => panic!("Expected File variant"),
@@ -1641,16 +2017,57 @@ mod tests {
         let uri = uri!("file:///path/to/file.rs#L1872");
         let parsed = MentionUri::parse(uri, PathStyle::local()).unwrap();
         match &parsed {
-            MentionUri::Selection {
-                abs_path: path,
-                line_range,
-                ..
-            } => {
-                assert_eq!(path.as_ref().unwrap(), Path::new(path!("/path/to/file.rs")));
-                assert_eq!(line_range.start(), &1871);
-                assert_eq!(line_range.end(), &1871);
+            MentionUri::File { abs_path, line } => {
+                assert_eq!(abs_path, Path::new(path!("/path/to/file.rs")));
+                assert_eq!(*line, Some(1871));
             }
-            _ => panic!("Expected Selection variant"),
+            _ => panic!("Expected File variant"),
+        }
+    }
+
+    #[test]
+    fn test_file_uri_with_line_round_trip() {
+        let file_uri = uri!("file:///a.rs#L42");
+        let parsed = MentionUri::parse(file_uri, PathStyle::local()).unwrap();
+        assert_eq!(
+            parsed,
+            MentionUri::File {
+                abs_path: PathBuf::from(path!("/a.rs")),
+                line: Some(41),
+            }
+        );
+        assert_eq!(parsed.to_uri().to_string(), file_uri);
+        assert_eq!(parsed.name(), "a.rs (42)");
+
+        // A single-line range stays a selection rather than collapsing into a file line.
+        let selection_uri = uri!("file:///a.rs#L42:42");
+        let parsed = MentionUri::parse(selection_uri, PathStyle::local()).unwrap();
+        assert!(
+            matches!(parsed, MentionUri::Selection { .. }),
+            "Expected Selection variant, got {parsed:?}"
+        );
+        assert_eq!(parsed.to_uri().to_string(), selection_uri);
+    }
+
+    #[test]
+    fn test_file_line_parses_the_same_from_uris_and_bare_paths() {
+        let path_style = PathStyle::local();
+        for (uri, bare) in [
+            (uri!("file:///a.rs#L42"), path!("/a.rs#L42")),
+            (uri!("file:///a.rs#L42:42"), path!("/a.rs#L42:42")),
+            (uri!("file:///a.rs#L10-20"), path!("/a.rs#L10-20")),
+        ] {
+            let parsed = MentionUri::parse(uri, path_style).unwrap();
+            assert_eq!(
+                MentionUri::parse(bare, path_style).unwrap(),
+                parsed,
+                "{bare}"
+            );
+            assert_eq!(
+                MentionUri::parse_hyperlink(bare, path_style).unwrap(),
+                parsed,
+                "{bare}"
+            );
         }
     }
 
@@ -1712,9 +2129,11 @@ mod tests {
         // Two files with the same name — should disambiguate with parent dir
         let file_a = MentionUri::File {
             abs_path: PathBuf::from(path!("/project/src/README.md")),
+            line: None,
         };
         let file_b = MentionUri::File {
             abs_path: PathBuf::from(path!("/project/docs/README.md")),
+            line: None,
         };
         assert_eq!(file_a.name(), "README.md");
         assert_eq!(file_b.name(), "README.md");
@@ -1725,9 +2144,11 @@ mod tests {
         // Files that still collide at one parent should grow further.
         let deep_a = MentionUri::File {
             abs_path: PathBuf::from(path!("/a/src/foo.rs")),
+            line: None,
         };
         let deep_b = MentionUri::File {
             abs_path: PathBuf::from(path!("/b/src/foo.rs")),
+            line: None,
         };
         assert_eq!(deep_a.disambiguated_name(1), "src/foo.rs");
         assert_eq!(deep_b.disambiguated_name(1), "src/foo.rs");
@@ -1766,6 +2187,7 @@ mod tests {
         // Edge case: file at filesystem root has no parent to show
         let root_file = MentionUri::File {
             abs_path: PathBuf::from(path!("/README.md")),
+            line: None,
         };
         assert_eq!(root_file.disambiguated_name(1), "README.md");
         assert_eq!(root_file.disambiguated_name(5), "README.md");
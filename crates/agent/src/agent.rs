@@ -6325,6 +6325,7 @@ mod internal_tests {
                         "b.md",
                         MentionUri::File {
                             abs_path: path!("/a/b.md").into(),
+                            line: None,
                         }
                         .to_uri()
                         .to_string(),
@@ -6354,6 +6355,7 @@ mod internal_tests {
         send.await.unwrap();
         let uri = MentionUri::File {
             abs_path: path!("/a/b.md").into(),
+            line: None,
         }
         .to_uri();
         acp_thread.read_with(cx, |thread, cx| {
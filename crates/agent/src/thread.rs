@@ -348,7 +348,7 @@ impl UserMessage {
                 }
                 UserMessageContent::Mention { uri, content } => {
                     match uri {
-                        MentionUri::File { abs_path } => {
+                        MentionUri::File { abs_path, .. } => {
                             write!(
                                 &mut file_context,
                                 "\n{}",
@@ -454,6 +454,19 @@ impl UserMessage {
                             )
                             .ok();
                         }
+                        MentionUri::GitBlob { abs_path, rev } => {
+                            write!(
+                                &mut file_context,
+                                "\n{} at revision {}:\n{}",
+                                abs_path.display(),
+                                rev,
+                                MarkdownCodeBlock {
+                                    tag: &codeblock_tag(abs_path, None),
+                                    text: content
+                                }
+                            )
+                            .ok();
+                        }
                         MentionUri::Skill { name, source, .. } => {
                             let label = format!("{} ({})", name, source);
                             write!(&mut skills_context, "\nSkill: {}\n{}\n", label, content).ok();
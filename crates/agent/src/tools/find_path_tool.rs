@@ -158,6 +158,7 @@ impl AgentTool for FindPathTool {
                             .map(|path| {
                                 let uri = MentionUri::File {
                                     abs_path: path.clone(),
+                                    line: None,
                                 };
                                 acp::ToolCallContent::Content(acp::Content::new(
                                     acp::ContentBlock::ResourceLink(acp::ResourceLink::new(
@@ -844,6 +844,7 @@ fn build_conflict_resolution_prompt(conflicts: &[ConflictContent]) -> Vec<acp::C
         )));
         let mention = MentionUri::File {
             abs_path: PathBuf::from(conflict.file_path.clone()),
+            line: None,
         };
         blocks.push(acp::ContentBlock::ResourceLink(acp::ResourceLink::new(
             mention.name(),
@@ -914,6 +915,7 @@ fn build_conflicted_files_resolution_prompt(
     for path in conflicted_file_paths {
         let mention = MentionUri::File {
             abs_path: PathBuf::from(path),
+            line: None,
         };
         content.push(acp::ContentBlock::ResourceLink(acp::ResourceLink::new(
             mention.name(),
@@ -9311,6 +9313,7 @@ mod tests {
 
         let expected_uri = MentionUri::File {
             abs_path: file_path,
+            line: None,
         }
         .to_uri()
         .to_string();
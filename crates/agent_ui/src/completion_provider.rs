@@ -664,7 +664,10 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
         let uri = if is_directory {
             MentionUri::Directory { abs_path }
         } else {
-            MentionUri::File { abs_path }
+            MentionUri::File {
+                abs_path,
+                line: None,
+            }
         };
 
         let crease_icon_path = uri.icon_path(cx);
@@ -1295,6 +1298,7 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
                     abs_path.as_ref().is_none_or(|path| {
                         !mentions.contains(&MentionUri::File {
                             abs_path: path.clone(),
+                            line: None,
                         })
                     })
                 })
@@ -1317,6 +1321,7 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
                                     path_prefix,
                                     is_dir: false,
                                     distance_to_relative_ancestor: 0,
+                                    matched_name: None,
                                 },
                                 is_recent: true,
                             })
@@ -2264,6 +2269,7 @@ pub(crate) fn search_files(
                         path: project_path.path,
                         path_prefix,
                         distance_to_relative_ancestor: 0,
+                        matched_name: None,
                         is_dir: false,
                     },
                     is_recent: true,
@@ -2285,6 +2291,7 @@ pub(crate) fn search_files(
                     path: entry.path.clone(),
                     path_prefix: path_prefix.clone(),
                     distance_to_relative_ancestor: 0,
+                    matched_name: None,
                     is_dir: entry.is_dir(),
                 },
                 is_recent: false,
@@ -2319,6 +2326,7 @@ pub(crate) fn search_files(
             fuzzy::match_path_sets(
                 candidate_sets.as_slice(),
                 query.as_str(),
+                None,
                 &relative_to,
                 false,
                 100,
@@ -3508,7 +3508,10 @@ impl AgentCodeSpanResolver {
                             .column
                             .map(|column| column.saturating_sub(1)),
                     },
-                    None => MentionUri::File { abs_path },
+                    None => MentionUri::File {
+                        abs_path,
+                        line: None,
+                    },
                 };
 
                 return Some(mention.to_uri().to_string().into());
@@ -3938,6 +3941,7 @@ pub(crate) mod tests {
             MentionUri::parse(&uri, PathStyle::local()).unwrap(),
             MentionUri::File {
                 abs_path: PathBuf::from(util::path!("/project/src/main.rs")),
+                line: None,
             }
         );
 
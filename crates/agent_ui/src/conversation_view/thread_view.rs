@@ -12271,8 +12271,14 @@ pub(crate) fn open_link(
             _ => mention,
         };
         workspace.update(cx, |workspace, cx| match mention {
-            MentionUri::File { abs_path } => {
-                open_abs_path_at_point(workspace, abs_path, None, window, cx);
+            MentionUri::File { abs_path, line } => {
+                open_abs_path_at_point(
+                    workspace,
+                    abs_path,
+                    line.map(|line| Point::new(line, 0)),
+                    window,
+                    cx,
+                );
             }
             MentionUri::PastedImage { .. } => {}
             MentionUri::Directory { abs_path } => {
@@ -12329,6 +12335,9 @@ pub(crate) fn open_link(
             MentionUri::TerminalSelection { .. } => {}
             MentionUri::GitDiff { .. } => {}
             MentionUri::MergeConflict { .. } => {}
+            MentionUri::GitBlob { abs_path, rev } => {
+                crate::ui::open_git_blob(workspace, abs_path, rev, window, cx);
+            }
             MentionUri::Rule { name, .. } => {
                 crate::ui::open_migrated_rule(workspace, &name, window, cx);
             }
@@ -94,6 +94,9 @@ fn blocks_have_user_content(blocks: &[acp::ContentBlock]) -> bool {
 
 /// Rewrites `[@Something](scheme://...)` mention links as `@Something` so the
 /// sidebar's draft-title preview doesn't show raw markdown link syntax.
+///
+/// Labels are unescaped and `<...>` destinations are skipped whole, matching the
+/// links written by [`acp_thread::MentionUri::as_link`].
 pub fn clean_mention_links(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
     let mut remaining = input;
@@ -101,14 +104,12 @@ pub fn clean_mention_links(input: &str) -> String {
     while let Some(start) = remaining.find("[@") {
         result.push_str(&remaining[..start]);
         let after_bracket = &remaining[start + 1..];
-        if let Some(close_bracket) = after_bracket.find("](") {
-            let mention = &after_bracket[..close_bracket];
-            let after_link_start = &after_bracket[close_bracket + 2..];
-            if let Some(close_paren) = after_link_start.find(')') {
-                result.push_str(mention);
-                remaining = &after_link_start[close_paren + 1..];
-                continue;
-            }
+        if let Some((mention, after_label)) = split_mention_label(after_bracket)
+            && let Some(after_link) = skip_link_destination(after_label)
+        {
+            result.push_str(&mention);
+            remaining = after_link;
+            continue;
         }
         result.push_str("[@");
         remaining = &remaining[start + 2..];
@@ -117,6 +118,40 @@ pub fn clean_mention_links(input: &str) -> String {
     result
 }
 
+/// Reads a link label up to its first unescaped `]`, returning the unescaped label
+/// and the text after the `]`.
+fn split_mention_label(text: &str) -> Option<(String, &str)> {
+    let mut label = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '\\' => match chars.peek() {
+                Some(&(_, escaped)) if escaped.is_ascii_punctuation() => {
+                    label.push(escaped);
+                    chars.next();
+                }
+                _ => label.push(char),
+            },
+            ']' => return Some((label, &text[index + 1..])),
+            _ => label.push(char),
+        }
+    }
+    None
+}
+
+/// Skips a `(destination)` at the start of `text`, returning the text after it.
+/// Destinations wrapped in `<...>` may contain `)`.
+fn skip_link_destination(text: &str) -> Option<&str> {
+    let destination = text.strip_prefix('(')?;
+    if let Some(destination) = destination.strip_prefix('<') {
+        let end = destination.find(">)")?;
+        Some(&destination[end + 2..])
+    } else {
+        let end = destination.find(')')?;
+        Some(&destination[end + 1..])
+    }
+}
+
 /// Collapses whitespace and truncates raw editor text for display as a draft
 /// label in the sidebar.
 pub fn truncate_draft_label(raw: &str) -> Option<SharedString> {
@@ -186,6 +221,9 @@ pub fn empty_draft_placeholder_label(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use acp_thread::MentionUri;
+    use std::path::PathBuf;
+    use util::path;
 
     #[test]
     fn test_clean_mention_links() {
@@ -222,4 +260,38 @@ mod tests {
         // Empty input.
         assert_eq!(clean_mention_links(""), "");
     }
+
+    #[test]
+    fn test_clean_mention_links_unescapes_labels_and_angle_destinations() {
+        let symbol = MentionUri::Symbol {
+            abs_path: PathBuf::from(path!("/path/to/lib.rs")),
+            name: "Index<[T]>".into(),
+            line_range: 0..=0,
+        };
+        assert_eq!(
+            clean_mention_links(&format!("see {} here", symbol.as_link())),
+            "see @Index<[T]> here"
+        );
+
+        let file = MentionUri::File {
+            abs_path: PathBuf::from(path!("/path/with space/f(1).rs")),
+            line: None,
+        };
+        assert_eq!(
+            clean_mention_links(&format!("open {} now", file.as_link())),
+            "open @f(1).rs now"
+        );
+
+        // A backslash that doesn't escape punctuation is kept, as in Windows paths.
+        assert_eq!(
+            clean_mention_links(r"[@C:\src\main.rs](file:///C:/src/main.rs)"),
+            r"@C:\src\main.rs"
+        );
+
+        // An unescaped `]` that doesn't start a link leaves the text alone.
+        assert_eq!(
+            clean_mention_links("[@foo] and [@bar.rs](file:///bar.rs)"),
+            "[@foo] and @bar.rs"
+        );
+    }
 }
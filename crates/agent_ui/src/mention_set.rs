@@ -138,7 +138,7 @@ impl MentionSet {
             MentionUri::Fetch { url } => self.confirm_mention_for_fetch(url, http_client, cx),
             MentionUri::Directory { .. } => Task::ready(Ok(Mention::Link)),
             MentionUri::Thread { id, .. } => self.confirm_mention_for_thread(id, cx),
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 self.confirm_mention_for_file(abs_path, supports_images, cx)
             }
             MentionUri::Symbol {
@@ -156,6 +156,9 @@ impl MentionSet {
             MentionUri::GitDiff { base_ref } => {
                 self.confirm_mention_for_git_diff(base_ref.into(), cx)
             }
+            MentionUri::GitBlob { abs_path, rev } => {
+                self.confirm_mention_for_git_blob(abs_path, rev, cx)
+            }
             MentionUri::Selection {
                 abs_path: Some(abs_path),
                 line_range,
@@ -256,7 +259,7 @@ impl MentionSet {
             start_anchor.to_offset(&snapshot.buffer_snapshot()) + content_len + 1usize,
         );
 
-        let crease = if let MentionUri::File { abs_path } = &mention_uri
+        let crease = if let MentionUri::File { abs_path, .. } = &mention_uri
             && is_raster_image_path(abs_path)
         {
             let Some(project_path) = project
@@ -312,7 +315,7 @@ impl MentionSet {
             }
             MentionUri::Directory { .. } => Task::ready(Ok(Mention::Link)),
             MentionUri::Thread { id, .. } => self.confirm_mention_for_thread(id, cx),
-            MentionUri::File { abs_path } => {
+            MentionUri::File { abs_path, .. } => {
                 self.confirm_mention_for_file(abs_path, supports_images, cx)
             }
             MentionUri::Symbol {
@@ -348,6 +351,9 @@ impl MentionSet {
                 debug_panic!("unexpected merge conflict URI");
                 Task::ready(Err(anyhow!("unexpected merge conflict URI")))
             }
+            MentionUri::GitBlob { abs_path, rev } => {
+                self.confirm_mention_for_git_blob(abs_path, rev, cx)
+            }
             MentionUri::Rule { .. } => {
                 debug_panic!("unexpected rule URI");
                 Task::ready(Err(anyhow!("unexpected rule URI")))
@@ -699,6 +705,42 @@ impl MentionSet {
             }
         })
     }
+
+    pub fn confirm_mention_for_git_blob(
+        &self,
+        abs_path: PathBuf,
+        rev: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Mention>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project not found")));
+        };
+
+        let Some((repo, repo_path)) = project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_abs_path(&abs_path, cx)
+        else {
+            return Task::ready(Err(anyhow!(
+                "no repository found for git blob mention {abs_path:?}"
+            )));
+        };
+
+        let content_receiver = repo.update(cx, |repo, _| {
+            repo.load_file_at_revision(rev.clone(), repo_path)
+        });
+
+        cx.spawn(async move |_, _| {
+            let content = content_receiver
+                .await??
+                .with_context(|| format!("{abs_path:?} does not exist at revision {rev}"))?;
+            Ok(Mention::Text {
+                content,
+                tracked_buffers: Vec::new(),
+            })
+        })
+    }
 }
 
 /// Computes disambiguated labels for a set of mentions, so that mentions sharing
@@ -828,6 +870,63 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_git_blob_mentions_load_file_at_revision(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({".git": {}, "file.rs": "working copy\n"}),
+        )
+        .await;
+        fs.set_head_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("file.rs", "committed\n".into())],
+            "deadbeef",
+        );
+        let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
+        cx.run_until_parked();
+        let mention_set = cx.new(|_cx| MentionSet::new(project.downgrade(), None));
+
+        let confirm_blob = |abs_path: &str, cx: &mut TestAppContext| {
+            mention_set.update(cx, |mention_set, cx| {
+                let http_client = project.read(cx).client().http_client();
+                mention_set.confirm_mention_for_uri(
+                    MentionUri::GitBlob {
+                        abs_path: abs_path.into(),
+                        rev: "HEAD".into(),
+                    },
+                    false,
+                    http_client,
+                    cx,
+                )
+            })
+        };
+
+        let mention = confirm_blob(path!("/project/file.rs"), cx).await.unwrap();
+        match mention {
+            Mention::Text {
+                content,
+                tracked_buffers,
+            } => {
+                assert_eq!(content, "committed\n");
+                assert!(tracked_buffers.is_empty());
+            }
+            other => panic!("Expected git blob mention to resolve as text, got {other:?}"),
+        }
+
+        let error = confirm_blob(path!("/project/missing.rs"), cx)
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("does not exist at revision HEAD"),
+            "Unexpected error: {error:#}"
+        );
+    }
+
     #[test]
     fn test_is_raster_image_path_is_case_insensitive() {
         // Regression test for #54308: drag-and-dropping a file whose extension
@@ -853,10 +952,12 @@ mod tests {
         // their full path. Distinct files sharing a base name still disambiguate.
         let foo_a = MentionUri::File {
             abs_path: path!("/project/a/foo.rs").into(),
+            line: None,
         };
 
         let foo_b = MentionUri::File {
             abs_path: path!("/project/b/foo.rs").into(),
+            line: None,
         };
 
         let uris = vec![&foo_a, &foo_a, &foo_b];
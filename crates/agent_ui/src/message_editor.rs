@@ -268,7 +268,10 @@ fn insert_mention_for_project_path(
         let mention_uri = if entry.is_dir() {
             MentionUri::Directory { abs_path }
         } else {
-            MentionUri::File { abs_path }
+            MentionUri::File {
+                abs_path,
+                line: None,
+            }
         };
         (file_name, mention_uri)
     };
@@ -2201,16 +2204,27 @@ fn parse_mention_links(text: &str, path_style: PathStyle) -> Vec<(Range<usize>,
             continue;
         }
 
-        // Find the matching closing parenthesis for the URI, handling nested parens
+        // Find the end of the URI. Destinations wrapped in `<...>` may contain unbalanced
+        // parens; bare ones may contain nested parens.
         let uri_start = name_end + 2;
-        let Some(uri_end_relative) = find_matching_bracket(&text[name_end + 1..], '(', ')') else {
-            search_start = uri_start;
-            continue;
+        let (uri_range, link_end) = if text[uri_start..].starts_with('<') {
+            let Some(uri_end_relative) = text[uri_start..].find(">)") else {
+                search_start = uri_start;
+                continue;
+            };
+            let uri_end = uri_start + uri_end_relative;
+            (uri_start + 1..uri_end, uri_end + 2)
+        } else {
+            let Some(uri_end_relative) = find_matching_bracket(&text[name_end + 1..], '(', ')')
+            else {
+                search_start = uri_start;
+                continue;
+            };
+            let uri_end = name_end + 1 + uri_end_relative;
+            (uri_start..uri_end, uri_end + 1)
         };
-        let uri_end = name_end + 1 + uri_end_relative;
-        let link_end = uri_end + 1;
 
-        let uri_str = &text[uri_start..uri_end];
+        let uri_str = &text[uri_range];
 
         // Try to parse the URI as a MentionUri
         if let Ok(mention_uri) = MentionUri::parse(uri_str, path_style) {
@@ -2223,13 +2237,22 @@ fn parse_mention_links(text: &str, path_style: PathStyle) -> Vec<(Range<usize>,
     mentions
 }
 
-/// Finds the position of the matching closing bracket, handling nested brackets.
+/// Finds the position of the matching closing bracket, handling nested brackets and
+/// skipping backslash-escaped characters, as written by [`MentionUri::as_link`].
 /// The input `text` should start with the opening bracket.
 /// Returns the index of the matching closing bracket relative to `text`.
 fn find_matching_bracket(text: &str, open: char, close: char) -> Option<usize> {
     let mut depth = 0;
-    for (index, character) in text.char_indices() {
-        if character == open {
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, character)) = chars.next() {
+        if character == '\\' {
+            if chars
+                .peek()
+                .is_some_and(|(_, next)| next.is_ascii_punctuation())
+            {
+                chars.next();
+            }
+        } else if character == open {
             depth += 1;
         } else if character == close {
             depth -= 1;
@@ -2527,6 +2550,19 @@ mod tests {
         } else {
             panic!("Expected Fetch URI");
         }
+
+        // Angle-bracket destinations may contain unbalanced parens
+        let text = "[@page.tsx](<file:///app/(group/page.tsx>) after";
+        let mentions = parse_mention_links(text, PathStyle::local());
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].0, 0..text.len() - " after".len());
+        assert!(matches!(mentions[0].1, MentionUri::File { .. }));
+
+        // Escaped brackets don't end the name early
+        let text = r"[@a\]b.rs](file:///a%5Db.rs)";
+        let mentions = parse_mention_links(text, PathStyle::local());
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].0, 0..text.len());
     }
 
     #[gpui::test]
@@ -3363,6 +3399,7 @@ mod tests {
 
         let url_one = MentionUri::File {
             abs_path: path!("/dir/a/one.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -3441,6 +3478,7 @@ mod tests {
 
         let url_eight = MentionUri::File {
             abs_path: path!("/dir/b/eight.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5129,6 +5167,7 @@ mod tests {
 
         let expected_uri = MentionUri::File {
             abs_path: path!("/project/file.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5147,10 +5186,51 @@ mod tests {
             uri,
             &MentionUri::File {
                 abs_path: path!("/project/file.txt").into(),
+                line: None,
             }
         );
     }
 
+    #[gpui::test]
+    async fn test_paste_mention_links_with_special_characters(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (message_editor, editor, mut cx) = setup_paste_test_message_editor(
+            json!({
+                "app": {"(group)": {"page.tsx": "content"}},
+                "a]b.rs": "content",
+            }),
+            cx,
+        )
+        .await;
+
+        let paren_uri = MentionUri::File {
+            abs_path: path!("/project/app/(group)/page.tsx").into(),
+            line: None,
+        };
+        let bracket_uri = MentionUri::File {
+            abs_path: path!("/project/a]b.rs").into(),
+            line: None,
+        };
+        let copied_text = format!("{} and {}", paren_uri.as_link(), bracket_uri.as_link());
+        assert!(copied_text.contains("](<file:///"));
+        assert!(copied_text.contains(r"[@a\]b.rs]"));
+
+        cx.write_to_clipboard(ClipboardItem::new_string(copied_text.clone()));
+        message_editor.update_in(&mut cx, |message_editor, window, cx| {
+            message_editor.paste(&Paste, window, cx);
+        });
+        cx.run_until_parked();
+
+        editor.update(&mut cx, |editor, cx| {
+            assert_eq!(editor.text(cx), copied_text);
+        });
+
+        let contents = mention_contents(&message_editor, &mut cx).await;
+        assert_eq!(contents.len(), 2);
+        assert!(contents.iter().any(|(uri, _)| uri == &paren_uri));
+        assert!(contents.iter().any(|(uri, _)| uri == &bracket_uri));
+    }
+
     #[gpui::test]
     async fn test_paste_external_directory_path_inserts_directory_mention(cx: &mut TestAppContext) {
         init_test(cx);
@@ -5213,6 +5293,7 @@ mod tests {
 
         let expected_uri = MentionUri::File {
             abs_path: path!("/project/file.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5242,6 +5323,7 @@ mod tests {
 
         let expected_uri = MentionUri::File {
             abs_path: path!("/project/file.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5263,6 +5345,7 @@ mod tests {
             uri,
             &MentionUri::File {
                 abs_path: path!("/project/file.txt").into(),
+                line: None,
             }
         );
     }
@@ -5290,11 +5373,13 @@ mod tests {
 
         let first_uri = MentionUri::File {
             abs_path: path!("/project/one.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
         let second_uri = MentionUri::File {
             abs_path: path!("/project/two.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5341,6 +5426,7 @@ mod tests {
 
         let expected_file_uri = MentionUri::File {
             abs_path: path!("/project/file.txt").into(),
+            line: None,
         }
         .to_uri()
         .to_string();
@@ -5366,6 +5452,7 @@ mod tests {
         assert!(contents.iter().any(|(uri, mention)| {
             *uri == MentionUri::File {
                 abs_path: path!("/project/file.txt").into(),
+                line: None,
             } && matches!(
                 mention,
                 Mention::Text {
@@ -2,6 +2,7 @@ use std::{path::PathBuf, time::Duration};
 
 use acp_thread::MentionUri;
 use agent_client_protocol::schema::v1 as acp;
+use anyhow::Context as _;
 use editor::Editor;
 use gpui::{
     Animation, AnimationExt, AnyView, Context, IntoElement, TaskExt, WeakEntity, Window,
@@ -12,7 +13,7 @@ use rope::Point;
 use settings::Settings;
 use theme_settings::ThemeSettings;
 use ui::{ButtonLike, TintColor, Tooltip, prelude::*};
-use workspace::{OpenOptions, Workspace};
+use workspace::{OpenOptions, Workspace, notifications::DetachAndPromptErr as _};
 
 use crate::open_abs_path_at_point;
 
@@ -159,8 +160,14 @@ fn open_mention_uri(
     };
 
     workspace.update(cx, |workspace, cx| match mention_uri {
-        MentionUri::File { abs_path } => {
-            open_abs_path_at_point(workspace, abs_path, None, window, cx);
+        MentionUri::File { abs_path, line } => {
+            open_abs_path_at_point(
+                workspace,
+                abs_path,
+                line.map(|line| Point::new(line, 0)),
+                window,
+                cx,
+            );
         }
         MentionUri::Symbol {
             abs_path,
@@ -188,6 +195,9 @@ fn open_mention_uri(
                 cx,
             );
         }
+        MentionUri::GitBlob { abs_path, rev } => {
+            open_git_blob(workspace, abs_path, rev, window, cx);
+        }
         MentionUri::Directory { abs_path } => {
             reveal_in_project_panel(workspace, abs_path, cx);
         }
@@ -339,6 +349,61 @@ fn open_skill_content_buffer(
     workspace.add_item(pane, Box::new(editor), None, true, true, window, cx);
 }
 
+/// Opens `abs_path` as of `rev` in a read-only buffer, rather than the working
+/// copy, so the agent's view of the file is what the user sees.
+pub(crate) fn open_git_blob(
+    workspace: &mut Workspace,
+    abs_path: PathBuf,
+    rev: String,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let languages = project.languages().clone();
+    let repo_and_path = project
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_abs_path(&abs_path, cx);
+    let Some((repo, repo_path)) = repo_and_path else {
+        workspace.show_error(
+            format!("No repository found for {}", abs_path.display()),
+            cx,
+        );
+        return;
+    };
+    let content = repo.update(cx, |repo, _| {
+        repo.load_file_at_revision(rev.clone(), repo_path)
+    });
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let content = content
+            .await??
+            .with_context(|| format!("{} does not exist at revision {rev}", abs_path.display()))?;
+        let language = languages.load_language_for_file_path(&abs_path).await.ok();
+        let title = MentionUri::GitBlob { abs_path, rev }.name();
+        workspace.update_in(cx, |workspace, window, cx| {
+            let buffer = cx.new(|cx| {
+                let mut buffer = Buffer::local(content, cx);
+                buffer.set_language(language, cx);
+                buffer
+            });
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::for_buffer(buffer, None, window, cx);
+                editor.set_read_only(true);
+                editor
+                    .buffer()
+                    .update(cx, |buffer, cx| buffer.set_title(title, cx));
+                editor
+            });
+            let pane = workspace.active_pane().clone();
+            workspace.add_item(pane, Box::new(editor), None, true, true, window, cx);
+        })
+    })
+    .detach_and_prompt_err("Failed to open file at revision", window, cx, |_, _, _| {
+        None
+    });
+}
+
 fn reveal_in_project_panel(
     workspace: &mut Workspace,
     abs_path: PathBuf,
@@ -734,7 +734,9 @@ fn matching_history_items<'a>(
                 worktree_root_name,
                 query.path_query(),
                 fuzzy_nucleo::Case::Ignore,
+                fuzzy_nucleo::MatchKind::Fuzzy,
                 max_results,
+                &AtomicBool::new(false),
                 path_style,
             )
             .into_iter()
@@ -1072,6 +1074,8 @@ impl FileFinderDelegate {
                 query.path_query(),
                 &relative_to,
                 fuzzy_nucleo::Case::Ignore,
+                fuzzy_nucleo::MatchKind::Fuzzy,
+                0.0,
                 100,
                 &cancel_flag,
                 cx.background_executor().clone(),
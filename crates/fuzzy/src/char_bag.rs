@@ -65,3 +65,26 @@ impl From<&[char]> for CharBag {
         bag
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_superset() {
+        let candidate = CharBag::from("src/main.rs");
+
+        // Subset
+        assert!(candidate.is_superset(CharBag::from("mn")));
+        // Equal
+        assert!(candidate.is_superset(CharBag::from("src/main.rs")));
+        assert!(candidate.is_superset(candidate));
+        // Case doesn't matter
+        assert!(candidate.is_superset(CharBag::from("MAIN")));
+        // Non-subset
+        assert!(!candidate.is_superset(CharBag::from("lib")));
+        // A repeated char needs at least as many occurrences in the candidate
+        assert!(!candidate.is_superset(CharBag::from("mm")));
+        assert!(candidate.is_superset(CharBag::from("ss")));
+    }
+}
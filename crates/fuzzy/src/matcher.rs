@@ -586,6 +586,7 @@ mod tests {
                 is_dir: false,
                 char_bag,
                 path: &path_arcs[i],
+                alt_names: &[],
             });
         }
 
@@ -607,6 +608,7 @@ mod tests {
                 path: candidate.path.into(),
                 path_prefix: RelPath::empty_arc(),
                 distance_to_relative_ancestor: usize::MAX,
+                matched_name: None,
                 is_dir: false,
             },
         );
@@ -1,6 +1,8 @@
 use gpui::BackgroundExecutor;
 use std::{
     cmp::{self, Ordering},
+    collections::{HashMap, HashSet, hash_map::Entry},
+    path::{Path, PathBuf},
     sync::{
         Arc,
         atomic::{self, AtomicBool},
@@ -19,11 +21,16 @@ pub struct PathMatchCandidate<'a> {
     pub is_dir: bool,
     pub path: &'a RelPath,
     pub char_bag: CharBag,
+    /// Other names the same entry can be found by, such as a directory name for its `index.ts`.
+    /// Each is scored alongside `path` and the best-scoring name is reported.
+    pub alt_names: &'a [&'a RelPath],
 }
 
 #[derive(Clone, Debug)]
 pub struct PathMatch {
     pub score: f64,
+    /// Character indices of the match in the worktree prefix followed by `path`, or
+    /// followed by [`Self::matched_name`] when that is set, so highlighters must check it.
     pub positions: Vec<usize>,
     pub worktree_id: usize,
     pub path: Arc<RelPath>,
@@ -32,6 +39,9 @@ pub struct PathMatch {
     /// Number of steps removed from a shared parent with the relative path
     /// Used to order closer paths first in the search list
     pub distance_to_relative_ancestor: usize,
+    /// The alternate name that matched, when it scored better than `path`.
+    /// `positions` index into this name instead of `path` when it is set.
+    pub matched_name: Option<Arc<RelPath>>,
 }
 
 pub trait PathMatchCandidateSet<'a>: Send + Sync {
@@ -47,6 +57,21 @@ pub trait PathMatchCandidateSet<'a>: Send + Sync {
     fn path_style(&self) -> PathStyle;
 }
 
+impl PathMatch {
+    /// Resolves this match against the absolute path of its worktree's root.
+    ///
+    /// `path_prefix` is only used for display and is not joined: when the worktree root is a
+    /// file, the prefix is that file's name and `path` is empty, so the root itself is the
+    /// match.
+    pub fn absolute_path(&self, worktree_root: &Path) -> PathBuf {
+        if self.path.is_empty() {
+            worktree_root.to_path_buf()
+        } else {
+            worktree_root.join(self.path.as_std_path())
+        }
+    }
+}
+
 impl<'a> MatchCandidate for PathMatchCandidate<'a> {
     fn has_chars(&self, bag: CharBag) -> bool {
         self.char_bag.is_superset(bag)
@@ -57,6 +82,23 @@ impl<'a> MatchCandidate for PathMatchCandidate<'a> {
     }
 }
 
+/// One of the names a [`PathMatchCandidate`] can be matched by.
+struct PathMatchCandidateName<'a> {
+    candidate: PathMatchCandidate<'a>,
+    name: &'a RelPath,
+    char_bag: CharBag,
+}
+
+impl MatchCandidate for PathMatchCandidateName<'_> {
+    fn has_chars(&self, bag: CharBag) -> bool {
+        self.char_bag.is_superset(bag)
+    }
+
+    fn candidate_chars(&self) -> impl Iterator<Item = char> {
+        self.name.as_unix_str().chars()
+    }
+}
+
 impl PartialEq for PathMatch {
     fn eq(&self, other: &Self) -> bool {
         self.cmp(other).is_eq()
@@ -83,6 +125,7 @@ impl Ord for PathMatch {
                     .cmp(&self.distance_to_relative_ancestor)
             })
             .then_with(|| self.path.cmp(&other.path))
+            .then_with(|| self.path_prefix.cmp(&other.path_prefix))
     }
 }
 
@@ -119,7 +162,8 @@ pub fn match_fixed_path_set(
         None => (RelPath::empty_arc(), Default::default(), Default::default()),
     };
 
-    matcher.match_candidates(
+    match_path_candidates(
+        &mut matcher,
         &path_prefix_chars,
         &lowercase_prefix,
         candidates.into_iter(),
@@ -133,15 +177,19 @@ pub fn match_fixed_path_set(
             path: candidate.path.into(),
             path_prefix: path_prefix.clone(),
             distance_to_relative_ancestor: usize::MAX,
+            matched_name: None,
         },
     );
     util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
     results
 }
 
+/// Matches `query` against every candidate in `candidate_sets`. When `extensions` is given,
+/// files with any other extension are skipped before scoring; directories are always kept.
 pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     candidate_sets: &'a [Set],
     query: &str,
+    extensions: Option<&[&str]>,
     relative_to: &Option<Arc<RelPath>>,
     smart_case: bool,
     max_results: usize,
@@ -201,7 +249,11 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                         if tree_start < segment_end && segment_start < tree_end {
                             let start = cmp::max(tree_start, segment_start) - tree_start;
                             let end = cmp::min(tree_end, segment_end) - tree_start;
-                            let candidates = candidate_set.candidates(start).take(end - start);
+                            let root_name = candidate_set.prefix();
+                            let candidates =
+                                candidate_set.candidates(start).take(end - start).filter(
+                                    |candidate| has_extension(candidate, &root_name, extensions),
+                                );
 
                             let worktree_id = candidate_set.id();
                             let mut prefix = candidate_set
@@ -216,7 +268,8 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                                 .iter()
                                 .map(|c| simple_lowercase(*c))
                                 .collect::<Vec<_>>();
-                            matcher.match_candidates(
+                            match_path_candidates(
+                                &mut matcher,
                                 &prefix,
                                 &lowercase_prefix,
                                 candidates,
@@ -238,6 +291,7 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                                             )
                                         },
                                     ),
+                                    matched_name: None,
                                 },
                             );
                         }
@@ -260,29 +314,417 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     results
 }
 
-/// Compute the distance from a given path to some other path
-/// If there is no shared path, returns usize::MAX
+/// Matches each candidate by its path and any alternate names, keeping at most one match per
+/// candidate: the one for its best-scoring name, preferring the primary path on ties. Matches
+/// of an alternate name record it in [`PathMatch::matched_name`].
+///
+/// Primary paths are matched as-is; only candidates that have alternate names pay for the
+/// second pass and the merge.
+fn match_path_candidates<'a>(
+    matcher: &mut Matcher<'_>,
+    prefix: &[char],
+    lowercase_prefix: &[char],
+    candidates: impl Iterator<Item = PathMatchCandidate<'a>>,
+    results: &mut Vec<PathMatch>,
+    cancel_flag: &AtomicBool,
+    build_match: impl Fn(&PathMatchCandidate<'a>, f64, &Vec<usize>) -> PathMatch,
+) {
+    let mut with_alt_names = Vec::new();
+    let candidates = candidates.inspect(|candidate| {
+        if !candidate.alt_names.is_empty() {
+            with_alt_names.push(candidate.clone());
+        }
+    });
+
+    let start = results.len();
+    matcher.match_candidates(
+        prefix,
+        lowercase_prefix,
+        candidates,
+        results,
+        cancel_flag,
+        |candidate: &PathMatchCandidate<'a>, score, positions| {
+            build_match(candidate, score, positions)
+        },
+    );
+    if with_alt_names.is_empty() {
+        return;
+    }
+
+    let names = with_alt_names.iter().flat_map(|candidate| {
+        candidate
+            .alt_names
+            .iter()
+            .map(move |&name| PathMatchCandidateName {
+                candidate: candidate.clone(),
+                name,
+                char_bag: CharBag::from(name.as_unix_str()),
+            })
+    });
+    let mut alt_matches = Vec::new();
+    matcher.match_candidates(
+        prefix,
+        lowercase_prefix,
+        names,
+        &mut alt_matches,
+        cancel_flag,
+        |name: &PathMatchCandidateName<'a>, score, positions| {
+            let mut path_match = build_match(&name.candidate, score, positions);
+            path_match.matched_name = Some(name.name.into());
+            path_match
+        },
+    );
+
+    let alt_paths = with_alt_names
+        .iter()
+        .map(|candidate| candidate.path)
+        .collect::<HashSet<_>>();
+    let mut match_ix_by_path = results[start..]
+        .iter()
+        .enumerate()
+        .filter(|(_, path_match)| alt_paths.contains(path_match.path.as_ref()))
+        .map(|(ix, path_match)| (path_match.path.clone(), start + ix))
+        .collect::<HashMap<_, _>>();
+    for alt_match in alt_matches {
+        match match_ix_by_path.entry(alt_match.path.clone()) {
+            Entry::Occupied(entry) => {
+                let best = &mut results[*entry.get()];
+                if alt_match.score > best.score {
+                    *best = alt_match;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(results.len());
+                results.push(alt_match);
+            }
+        }
+    }
+}
+
+/// The root of a single-file worktree has an empty path, so its extension is taken from
+/// `root_name` instead.
+fn has_extension(
+    candidate: &PathMatchCandidate,
+    root_name: &RelPath,
+    extensions: Option<&[&str]>,
+) -> bool {
+    let Some(extensions) = extensions else {
+        return true;
+    };
+    let path = if candidate.path.is_empty() {
+        root_name
+    } else {
+        candidate.path
+    };
+    candidate.is_dir
+        || path
+            .extension()
+            .is_some_and(|extension| extensions.contains(&extension))
+}
+
+/// Compute the distance from a given path to some other path: the number of components
+/// both paths have below their longest shared ancestor. Identical paths are 0 apart.
 fn distance_between_paths(path: &RelPath, relative_to: &RelPath) -> usize {
-    let mut path_components = path.components();
-    let mut relative_components = relative_to.components();
-
-    while path_components
-        .next()
-        .zip(relative_components.next())
-        .map(|(path_component, relative_component)| path_component == relative_component)
-        .unwrap_or_default()
-    {}
-    path_components.count() + relative_components.count() + 1
+    let mut path_components = path.components().peekable();
+    let mut relative_components = relative_to.components().peekable();
+
+    while let (Some(path_component), Some(relative_component)) =
+        (path_components.peek(), relative_components.peek())
+        && path_component == relative_component
+    {
+        path_components.next();
+        relative_components.next();
+    }
+    path_components.count() + relative_components.count()
 }
 
 #[cfg(test)]
 mod tests {
-    use util::rel_path::RelPath;
+    use std::{
+        path::Path,
+        sync::{Arc, atomic::AtomicBool},
+    };
+
+    use gpui::BackgroundExecutor;
+    use util::{
+        paths::PathStyle,
+        rel_path::{RelPath, rel_path},
+    };
+
+    use super::{
+        PathMatch, PathMatchCandidate, PathMatchCandidateSet, distance_between_paths,
+        match_path_sets,
+    };
+    use crate::CharBag;
+
+    #[derive(Default)]
+    struct TestCandidateSet {
+        entries: Vec<(&'static RelPath, Vec<&'static RelPath>)>,
+        /// The file name of a single-file worktree's root.
+        root_file: Option<&'static RelPath>,
+    }
+
+    impl<'a> PathMatchCandidateSet<'a> for TestCandidateSet {
+        type Candidates = std::vec::IntoIter<PathMatchCandidate<'a>>;
+
+        fn id(&self) -> usize {
+            0
+        }
 
-    use super::distance_between_paths;
+        fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        fn root_is_file(&self) -> bool {
+            self.root_file.is_some()
+        }
+
+        fn prefix(&self) -> Arc<RelPath> {
+            self.root_file
+                .map_or_else(RelPath::empty_arc, |root_file| root_file.into_arc())
+        }
+
+        fn candidates(&'a self, start: usize) -> Self::Candidates {
+            self.entries[start..]
+                .iter()
+                .map(|(path, alt_names)| {
+                    // Like worktree entries, the char bag covers the root name of a
+                    // single-file worktree.
+                    let name = self.root_file.filter(|_| path.is_empty()).unwrap_or(*path);
+                    PathMatchCandidate {
+                        is_dir: false,
+                        path,
+                        char_bag: CharBag::from(name.as_unix_str()),
+                        alt_names,
+                    }
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn path_style(&self) -> PathStyle {
+            PathStyle::Unix
+        }
+    }
+
+    fn path_match(path_prefix: &str, path: &str) -> PathMatch {
+        PathMatch {
+            score: 1.0,
+            positions: Vec::new(),
+            worktree_id: 0,
+            path: rel_path(path).into_arc(),
+            path_prefix: rel_path(path_prefix).into_arc(),
+            is_dir: false,
+            distance_to_relative_ancestor: usize::MAX,
+            matched_name: None,
+        }
+    }
 
     #[test]
     fn test_distance_between_paths_empty() {
-        distance_between_paths(RelPath::empty(), RelPath::empty());
+        assert_eq!(
+            distance_between_paths(RelPath::empty(), RelPath::empty()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_distance_between_paths() {
+        let distance =
+            |path, relative_to| distance_between_paths(rel_path(path), rel_path(relative_to));
+        assert_eq!(distance("src/main.rs", "src/main.rs"), 0);
+        assert_eq!(distance("src/main.rs", "src/lib.rs"), 2);
+        assert_eq!(distance("src", "src/main.rs"), 1);
+        assert_eq!(distance("src/main.rs", "src"), 1);
+        assert_eq!(distance("src/a/b.rs", "src/c.rs"), 3);
+        assert_eq!(distance("a.rs", "b/c.rs"), 3);
+    }
+
+    #[test]
+    fn test_absolute_path_for_file_root() {
+        let path_match = path_match("main.rs", "");
+        assert_eq!(
+            path_match.absolute_path(Path::new("/code/main.rs")),
+            Path::new("/code/main.rs")
+        );
+    }
+
+    #[test]
+    fn test_absolute_path_for_directory_root() {
+        let worktree_root = Path::new("/code/project");
+        assert_eq!(
+            path_match("", "src/main.rs").absolute_path(worktree_root),
+            Path::new("/code/project/src/main.rs")
+        );
+        assert_eq!(
+            path_match("project", "src/main.rs").absolute_path(worktree_root),
+            Path::new("/code/project/src/main.rs")
+        );
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_matches_alt_names(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            entries: vec![
+                (
+                    rel_path("widgets/index.ts"),
+                    vec![rel_path("widgets/carousel")],
+                ),
+                (rel_path("widgets/button.ts"), Vec::new()),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let matches = match_path_sets(
+            &candidate_sets,
+            "carousel",
+            None,
+            &None,
+            false,
+            10,
+            &AtomicBool::new(false),
+            executor.clone(),
+        )
+        .await;
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path.as_ref(), rel_path("widgets/index.ts"));
+        assert_eq!(
+            matches[0].matched_name.as_deref(),
+            Some(rel_path("widgets/carousel"))
+        );
+        assert_eq!(matches[0].positions, (8..16).collect::<Vec<_>>());
+
+        let matches = match_path_sets(
+            &candidate_sets,
+            "index",
+            None,
+            &None,
+            false,
+            10,
+            &AtomicBool::new(false),
+            executor.clone(),
+        )
+        .await;
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].matched_name, None);
+
+        // A candidate matched by both its path and an alternate name is reported once.
+        let matches = match_path_sets(
+            &candidate_sets,
+            "widgets",
+            None,
+            &None,
+            false,
+            10,
+            &AtomicBool::new(false),
+            executor,
+        )
+        .await;
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            matches
+                .iter()
+                .filter(|path_match| path_match.path.as_ref() == rel_path("widgets/index.ts"))
+                .count(),
+            1
+        );
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_filters_by_extension(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            entries: vec![
+                (rel_path("src/main.rs"), Vec::new()),
+                (rel_path("Cargo.toml"), Vec::new()),
+                (rel_path("src/mod.rs"), Vec::new()),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let cancel_flag = AtomicBool::new(false);
+        let all_matches = match_path_sets(
+            &candidate_sets,
+            "m",
+            None,
+            &None,
+            false,
+            10,
+            &cancel_flag,
+            executor.clone(),
+        )
+        .await;
+        assert_eq!(all_matches.len(), 3);
+
+        let rust_matches = match_path_sets(
+            &candidate_sets,
+            "m",
+            Some(&["rs"]),
+            &None,
+            false,
+            10,
+            &cancel_flag,
+            executor,
+        )
+        .await;
+        assert_eq!(rust_matches.len(), 2);
+        for rust_match in &rust_matches {
+            assert!(rust_match.path.as_unix_str().ends_with(".rs"));
+            let unfiltered_match = all_matches
+                .iter()
+                .find(|path_match| path_match.path == rust_match.path)
+                .unwrap();
+            assert_eq!(rust_match.score, unfiltered_match.score);
+        }
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_filters_single_file_worktrees_by_root_name(
+        executor: BackgroundExecutor,
+    ) {
+        let single_file_set = |root_file| TestCandidateSet {
+            entries: vec![(RelPath::empty(), Vec::new())],
+            root_file: Some(rel_path(root_file)),
+        };
+        let candidate_sets = [single_file_set("main.rs"), single_file_set("Cargo.toml")];
+        let matches = match_path_sets(
+            &candidate_sets,
+            "m",
+            Some(&["rs"]),
+            &None,
+            false,
+            10,
+            &AtomicBool::new(false),
+            executor,
+        )
+        .await;
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path_prefix.as_ref(), rel_path("main.rs"));
+    }
+
+    #[test]
+    fn test_path_match_ordering_breaks_ties_on_path_prefix() {
+        let path_match = |path_prefix: &str| PathMatch {
+            score: 1.0,
+            positions: Vec::new(),
+            worktree_id: 0,
+            path: rel_path("src/main.rs").into_arc(),
+            path_prefix: rel_path(path_prefix).into_arc(),
+            is_dir: false,
+            distance_to_relative_ancestor: usize::MAX,
+            matched_name: None,
+        };
+
+        let mut matches = vec![path_match("zed"), path_match("alpha"), path_match("beta")];
+        matches.sort();
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| m.path_prefix.as_unix_str())
+                .collect::<Vec<_>>(),
+            vec!["alpha", "beta", "zed"]
+        );
+        assert_ne!(path_match("alpha"), path_match("beta"));
     }
 }
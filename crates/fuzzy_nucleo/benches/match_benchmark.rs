@@ -251,6 +251,7 @@ fn generate_fuzzy_path_candidates(
             is_dir: false,
             path: RelPath::from_unix_str(path).unwrap(),
             char_bag: CharBag::from(path.as_str()),
+            alt_names: &[],
         })
         .collect()
 }
@@ -305,7 +306,9 @@ fn bench_path_matching(criterion: &mut Criterion) {
                             None,
                             query,
                             case,
+                            fuzzy_nucleo::MatchKind::Fuzzy,
                             size,
+                            &AtomicBool::new(false),
                             PathStyle::Unix,
                         )
                     },
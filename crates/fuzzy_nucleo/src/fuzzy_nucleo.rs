@@ -7,6 +7,7 @@ use nucleo::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
 
 pub use paths::{
     PathMatch, PathMatchCandidate, PathMatchCandidateSet, match_fixed_path_set, match_path_sets,
+    match_path_sets_scores_only,
 };
 pub use strings::{StringMatch, StringMatchCandidate, match_strings, match_strings_async};
 
@@ -16,6 +17,9 @@ pub(crate) struct Cancelled;
 pub enum Case {
     Smart,
     Ignore,
+    /// Only matches candidates whose matched characters have the query's case,
+    /// so that `readme` doesn't match `README`.
+    Exact,
 }
 
 impl Case {
@@ -32,6 +36,28 @@ impl Case {
     }
 }
 
+/// How each whitespace-separated word of the query is matched against a candidate.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The word's characters must appear in order, possibly with gaps.
+    #[default]
+    Fuzzy,
+    /// The word must appear contiguously somewhere in the candidate.
+    Substring,
+    /// The word must equal the entire candidate.
+    Exact,
+}
+
+impl MatchKind {
+    fn atom_kind(self) -> AtomKind {
+        match self {
+            Self::Fuzzy => AtomKind::Fuzzy,
+            Self::Substring => AtomKind::Substring,
+            Self::Exact => AtomKind::Exact,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum LengthPenalty {
     On,
@@ -48,13 +74,14 @@ impl LengthPenalty {
     }
 }
 
-// Matching is always case-insensitive at the nucleo level — using
-// `CaseMatching::Smart` there would *reject* candidates whose capitalization
-// doesn't match the query, breaking pickers like the command palette
-// (`"Editor: Backspace"` against the action named `"editor: backspace"`).
-// `Case::Smart` is honored as a *scoring hint* instead: when the query
-// contains uppercase, candidates whose matched characters disagree in case
-// are downranked by a per-mismatch penalty rather than dropped.
+// Matching is case-insensitive at the nucleo level unless `Case::Exact` is
+// requested — using `CaseMatching::Smart` there would *reject* candidates
+// whose capitalization doesn't match the query, breaking pickers like the
+// command palette (`"Editor: Backspace"` against the action named
+// `"editor: backspace"`). `Case::Smart` is honored as a *scoring hint*
+// instead: when the query contains uppercase, candidates whose matched
+// characters disagree in case are downranked by a per-mismatch penalty rather
+// than dropped.
 pub(crate) struct Query {
     pub(crate) pattern: Pattern,
     /// Non-whitespace query chars in input order, populated only when a smart-case
@@ -62,27 +89,40 @@ pub(crate) struct Query {
     /// `Pattern::indices` (atom-order, needle-order within each atom).
     pub(crate) query_chars: Option<Vec<char>>,
     pub(crate) char_bag: CharBag,
+    /// The query's chars when it could spell out the humps of a CamelCase name, like
+    /// `hmc` for `HttpMiddlewareController`: a single fuzzy word of two or more letters
+    /// or digits.
+    pub(crate) acronym_chars: Option<Vec<char>>,
 }
 
 impl Query {
-    pub(crate) fn build(query: &str, case: Case) -> Option<Self> {
+    pub(crate) fn build(query: &str, case: Case, match_kind: MatchKind) -> Option<Self> {
         if query.chars().all(char::is_whitespace) {
             return None;
         }
         let normalized = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        let case_matching = match case {
+            Case::Exact => CaseMatching::Respect,
+            Case::Smart | Case::Ignore => CaseMatching::Ignore,
+        };
         let pattern = Pattern::new(
             &normalized,
-            CaseMatching::Ignore,
+            case_matching,
             Normalization::Smart,
-            AtomKind::Fuzzy,
+            match_kind.atom_kind(),
         );
         let wants_case_penalty = case.is_smart() && query.chars().any(|c| c.is_uppercase());
         let query_chars =
             wants_case_penalty.then(|| query.chars().filter(|c| !c.is_whitespace()).collect());
+        let is_acronym_like = match_kind == MatchKind::Fuzzy
+            && normalized.chars().count() >= 2
+            && normalized.chars().all(char::is_alphanumeric);
+        let acronym_chars = is_acronym_like.then(|| normalized.chars().collect());
         Some(Query {
             pattern,
             query_chars,
             char_bag: CharBag::from(query),
+            acronym_chars,
         })
     }
 }
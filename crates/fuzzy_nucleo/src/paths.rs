@@ -14,7 +14,9 @@ use nucleo::pattern::Pattern;
 use fuzzy::CharBag;
 
 use crate::matcher::{self, LENGTH_PENALTY};
-use crate::{Cancelled, Case, Query, case_penalty, count_case_mismatches, positions_from_sorted};
+use crate::{
+    Cancelled, Case, MatchKind, Query, case_penalty, count_case_mismatches, positions_from_sorted,
+};
 
 #[derive(Clone, Debug)]
 pub struct PathMatchCandidate<'a> {
@@ -93,20 +95,22 @@ impl Ord for PathMatch {
                     .cmp(&self.distance_to_relative_ancestor)
             })
             .then_with(|| self.path.cmp(&other.path))
+            .then_with(|| self.path_prefix.cmp(&other.path_prefix))
     }
 }
 
 pub(crate) fn distance_between_paths(path: &RelPath, relative_to: &RelPath) -> usize {
-    let mut path_components = path.components();
-    let mut relative_components = relative_to.components();
-
-    while path_components
-        .next()
-        .zip(relative_components.next())
-        .map(|(path_component, relative_component)| path_component == relative_component)
-        .unwrap_or_default()
-    {}
-    path_components.count() + relative_components.count() + 1
+    let mut path_components = path.components().peekable();
+    let mut relative_components = relative_to.components().peekable();
+
+    while let (Some(path_component), Some(relative_component)) =
+        (path_components.peek(), relative_components.peek())
+        && path_component == relative_component
+    {
+        path_components.next();
+        relative_components.next();
+    }
+    path_components.count() + relative_components.count()
 }
 
 #[inline]
@@ -134,6 +138,87 @@ fn get_filename_match_bonus(
     score as f64 / filename.len().max(1) as f64
 }
 
+const ACRONYM_BONUS_PER_HUMP: f64 = 4.0;
+
+/// Yields the char offset and char of each CamelCase hump start in the file name of
+/// `candidate_buf`.
+fn filename_humps(candidate_buf: &str) -> impl Iterator<Item = (u32, char)> + '_ {
+    let filename_start = candidate_buf.rfind('/').map_or(0, |ix| ix + 1);
+    let mut char_ix = candidate_buf[..filename_start].chars().count() as u32;
+    let mut filename_chars = candidate_buf[filename_start..].chars().peekable();
+    let mut previous: Option<char> = None;
+    std::iter::from_fn(move || {
+        while let Some(ch) = filename_chars.next() {
+            let is_hump_start = match previous {
+                None => ch.is_alphanumeric(),
+                // `HTTPServer` has humps at `H` and `S`.
+                Some(previous) => {
+                    ch.is_uppercase()
+                        && (previous.is_lowercase()
+                            || previous.is_ascii_digit()
+                            || (previous.is_uppercase()
+                                && filename_chars
+                                    .peek()
+                                    .is_some_and(|next| next.is_lowercase())))
+                }
+            };
+            let ix = char_ix;
+            previous = Some(ch);
+            char_ix += 1;
+            if is_hump_start {
+                return Some((ix, ch));
+            }
+        }
+        None
+    })
+}
+
+/// Rewards queries that spell out the CamelCase humps of the file name, so that `hmc`
+/// prefers `HttpMiddlewareController.rs` over an incidental `ahmc.rs`. Only applies when
+/// every matched character lands on a hump start in the file name, so ordinary queries
+/// like `main` or `fileFinder` keep their plain nucleo scores.
+/// Expects `sorted_matched_chars` to be sorted ascending and deduplicated.
+#[inline]
+fn get_acronym_match_bonus(candidate_buf: &str, sorted_matched_chars: &[u32]) -> f64 {
+    // A single hump hit is just a prefix match, which nucleo already rewards.
+    if sorted_matched_chars.len() < 2 {
+        return 0.0;
+    }
+    let mut humps = filename_humps(candidate_buf).map(|(ix, _)| ix);
+    if sorted_matched_chars
+        .iter()
+        .all(|&position| humps.any(|hump| hump == position))
+    {
+        sorted_matched_chars.len() as f64 * ACRONYM_BONUS_PER_HUMP
+    } else {
+        0.0
+    }
+}
+
+/// Whether `acronym_chars` could all land on hump starts of the file name, which
+/// [`get_acronym_match_bonus`] requires. Cheaper than tracing the match, so candidates
+/// that can't earn the bonus are scored without positions. Non-ASCII chars are assumed
+/// to match, since nucleo normalizes them.
+fn could_spell_filename_humps(candidate_buf: &str, acronym_chars: &[char]) -> bool {
+    let mut humps = filename_humps(candidate_buf).map(|(_, ch)| ch);
+    acronym_chars.iter().all(|&query_char| {
+        humps.any(|hump| {
+            !hump.is_ascii() || !query_char.is_ascii() || hump.eq_ignore_ascii_case(&query_char)
+        })
+    })
+}
+
+/// Scales a match's score up by `proximity_weight` for a path sharing a directory with the
+/// relative path, falling off as the paths grow further apart.
+#[inline]
+fn proximity_boost(proximity_weight: f64, distance_to_relative_ancestor: usize) -> f64 {
+    if proximity_weight <= 0.0 || distance_to_relative_ancestor == usize::MAX {
+        1.0
+    } else {
+        1.0 + proximity_weight / distance_to_relative_ancestor.max(1) as f64
+    }
+}
+
 fn path_match_helper<'a>(
     matcher: &mut nucleo::Matcher,
     query: &Query,
@@ -144,7 +229,9 @@ fn path_match_helper<'a>(
     root_is_file: bool,
     relative_to: &Option<Arc<RelPath>>,
     path_style: PathStyle,
+    proximity_weight: f64,
     cancel_flag: &AtomicBool,
+    collect_positions: bool,
 ) -> Result<(), Cancelled> {
     let mut candidate_buf = if !path_prefix.is_empty() && !root_is_file {
         let mut s = path_prefix.display(path_style).to_string();
@@ -177,25 +264,54 @@ fn path_match_helper<'a>(
 
         let haystack = Utf32Str::new(&candidate_buf, &mut buf);
 
-        let Some(score) = query.pattern.indices(haystack, matcher, &mut matched_chars) else {
-            continue;
+        // The case penalty and acronym bonus depend on where the query matched, so positions
+        // are traced whenever the candidate could earn either, even if they aren't returned.
+        let trace_positions = collect_positions
+            || query.query_chars.is_some()
+            || query
+                .acronym_chars
+                .as_deref()
+                .is_some_and(|chars| could_spell_filename_humps(&candidate_buf, chars));
+        let (score, case_mismatches, acronym_bonus) = if trace_positions {
+            let Some(score) = query.pattern.indices(haystack, matcher, &mut matched_chars) else {
+                continue;
+            };
+            let case_mismatches = count_case_mismatches(
+                query.query_chars.as_deref(),
+                &matched_chars,
+                &candidate_buf,
+                &mut candidate_chars,
+            );
+            matched_chars.sort_unstable();
+            matched_chars.dedup();
+            let acronym_bonus = if query.acronym_chars.is_some() {
+                get_acronym_match_bonus(&candidate_buf, &matched_chars)
+            } else {
+                0.0
+            };
+            (score, case_mismatches, acronym_bonus)
+        } else {
+            let Some(score) = query.pattern.score(haystack, matcher) else {
+                continue;
+            };
+            (score, 0, 0.0)
         };
 
-        let case_mismatches = count_case_mismatches(
-            query.query_chars.as_deref(),
-            &matched_chars,
-            &candidate_buf,
-            &mut candidate_chars,
-        );
-
-        matched_chars.sort_unstable();
-        matched_chars.dedup();
-
         let length_penalty = candidate_buf.len() as f64 * LENGTH_PENALTY;
         let filename_bonus = get_filename_match_bonus(&candidate_buf, &query.pattern, matcher);
-        let positive = (score as f64 + filename_bonus) * case_penalty(case_mismatches);
+        let distance_to_relative_ancestor =
+            relative_to.as_ref().map_or(usize::MAX, |relative_to| {
+                distance_between_paths(candidate.path, relative_to.as_ref())
+            });
+        let positive = (score as f64 + filename_bonus + acronym_bonus)
+            * case_penalty(case_mismatches)
+            * proximity_boost(proximity_weight, distance_to_relative_ancestor);
         let adjusted_score = positive - length_penalty;
-        let positions = positions_from_sorted(&candidate_buf, &matched_chars);
+        let positions = if collect_positions {
+            positions_from_sorted(&candidate_buf, &matched_chars)
+        } else {
+            Vec::new()
+        };
 
         results.push(PathMatch {
             score: adjusted_score,
@@ -212,24 +328,27 @@ fn path_match_helper<'a>(
                 Arc::clone(path_prefix)
             },
             is_dir: candidate.is_dir,
-            distance_to_relative_ancestor: relative_to.as_ref().map_or(usize::MAX, |relative_to| {
-                distance_between_paths(candidate.path, relative_to.as_ref())
-            }),
+            distance_to_relative_ancestor,
         });
     }
     Ok(())
 }
 
+/// Matches `query` against a fixed list of candidates from a single worktree.
+///
+/// Returns no matches if `cancel_flag` is set before matching completes.
 pub fn match_fixed_path_set(
     candidates: Vec<PathMatchCandidate>,
     worktree_id: usize,
     worktree_root_name: Option<Arc<RelPath>>,
     query: &str,
     case: Case,
+    match_kind: MatchKind,
     max_results: usize,
+    cancel_flag: &AtomicBool,
     path_style: PathStyle,
 ) -> Vec<PathMatch> {
-    let Some(query) = Query::build(query, case) else {
+    let Some(query) = Query::build(query, case, match_kind) else {
         return Vec::new();
     };
 
@@ -243,7 +362,7 @@ pub fn match_fixed_path_set(
 
     let mut results = Vec::new();
 
-    path_match_helper(
+    let result = path_match_helper(
         &mut matcher,
         &query,
         candidates.into_iter(),
@@ -253,22 +372,92 @@ pub fn match_fixed_path_set(
         root_is_file,
         &None,
         path_style,
-        &AtomicBool::new(false),
-    )
-    .ok();
-    util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
+        0.0,
+        cancel_flag,
+        true,
+    );
     matcher::return_matcher(matcher);
+    if result.is_err() {
+        return Vec::new();
+    }
+    util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
     results
 }
 
+/// Matches `query` against every path in `candidate_sets`, returning the best `max_results`.
+///
+/// Matches are ordered by score, with paths closer to `relative_to` winning ties. A positive
+/// `proximity_weight` also folds that closeness into the score itself, so that a nearby path can
+/// outrank a slightly better match elsewhere in the project. `0.0` leaves scores untouched.
 pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     candidate_sets: &'a [Set],
     query: &str,
     relative_to: &Option<Arc<RelPath>>,
     case: Case,
+    match_kind: MatchKind,
+    proximity_weight: f64,
+    max_results: usize,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+) -> Vec<PathMatch> {
+    match_path_sets_internal(
+        candidate_sets,
+        query,
+        relative_to,
+        case,
+        match_kind,
+        proximity_weight,
+        max_results,
+        cancel_flag,
+        executor,
+        true,
+    )
+    .await
+}
+
+/// Like [`match_path_sets`], but leaves [`PathMatch::positions`] empty, for callers that only
+/// rank matches and never highlight them.
+///
+/// Skipping positions lets nucleo score most candidates without tracing the match, which is
+/// much cheaper on large worktrees. Candidates that could earn the smart-case penalty or the
+/// acronym bonus are still traced, so scores and rankings match [`match_path_sets`] exactly.
+pub async fn match_path_sets_scores_only<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: &Option<Arc<RelPath>>,
+    case: Case,
+    match_kind: MatchKind,
+    proximity_weight: f64,
+    max_results: usize,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+) -> Vec<PathMatch> {
+    match_path_sets_internal(
+        candidate_sets,
+        query,
+        relative_to,
+        case,
+        match_kind,
+        proximity_weight,
+        max_results,
+        cancel_flag,
+        executor,
+        false,
+    )
+    .await
+}
+
+async fn match_path_sets_internal<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: &Option<Arc<RelPath>>,
+    case: Case,
+    match_kind: MatchKind,
+    proximity_weight: f64,
     max_results: usize,
     cancel_flag: &AtomicBool,
     executor: BackgroundExecutor,
+    collect_positions: bool,
 ) -> Vec<PathMatch> {
     let path_count: usize = candidate_sets.iter().map(|s| s.len()).sum();
     if path_count == 0 {
@@ -283,7 +472,7 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
         query.to_owned()
     };
 
-    let Some(query) = Query::build(&query, case) else {
+    let Some(query) = Query::build(&query, case, match_kind) else {
         return Vec::new();
     };
 
@@ -327,7 +516,9 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                                 candidate_set.root_is_file(),
                                 &relative_to,
                                 path_style,
+                                proximity_weight,
                                 cancel_flag,
+                                collect_positions,
                             )
                             .is_err()
                             {
@@ -354,3 +545,515 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
     results
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::rel_path::rel_path;
+
+    #[derive(Default)]
+    struct TestCandidateSet {
+        paths: Vec<&'static RelPath>,
+        /// The worktree root name shown before each path.
+        prefix: Option<&'static RelPath>,
+    }
+
+    impl<'a> PathMatchCandidateSet<'a> for TestCandidateSet {
+        type Candidates = std::vec::IntoIter<PathMatchCandidate<'a>>;
+
+        fn id(&self) -> usize {
+            0
+        }
+
+        fn len(&self) -> usize {
+            self.paths.len()
+        }
+
+        fn root_is_file(&self) -> bool {
+            false
+        }
+
+        fn prefix(&self) -> Arc<RelPath> {
+            self.prefix
+                .map_or_else(RelPath::empty_arc, |prefix| prefix.into_arc())
+        }
+
+        fn candidates(&'a self, start: usize) -> Self::Candidates {
+            self.paths[start..]
+                .iter()
+                .map(|path| PathMatchCandidate::new(path, false, None))
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn path_style(&self) -> PathStyle {
+            PathStyle::Unix
+        }
+    }
+
+    #[test]
+    fn test_acronym_match_ranks_above_incidental_substring() {
+        let paths = [
+            rel_path("src/ahmc.rs"),
+            rel_path("src/html_macros.rs"),
+            rel_path("src/HttpMiddlewareController.rs"),
+        ];
+        let candidates = paths
+            .iter()
+            .map(|path| PathMatchCandidate::new(path, false, None))
+            .collect();
+
+        let results = match_fixed_path_set(
+            candidates,
+            0,
+            None,
+            "hmc",
+            Case::Ignore,
+            MatchKind::Fuzzy,
+            10,
+            &AtomicBool::new(false),
+            PathStyle::Unix,
+        );
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].path.as_ref(),
+            rel_path("src/HttpMiddlewareController.rs")
+        );
+    }
+
+    #[test]
+    fn test_exact_case_does_not_fold_case() {
+        let paths = [rel_path("README.md"), rel_path("docs/readme.md")];
+        let candidates = || {
+            paths
+                .iter()
+                .map(|path| PathMatchCandidate::new(path, false, None))
+                .collect::<Vec<_>>()
+        };
+
+        let results = match_fixed_path_set(
+            candidates(),
+            0,
+            None,
+            "readme",
+            Case::Exact,
+            MatchKind::Fuzzy,
+            10,
+            &AtomicBool::new(false),
+            PathStyle::Unix,
+        );
+        let matched_paths = results
+            .iter()
+            .map(|result| result.path.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(matched_paths, vec![rel_path("docs/readme.md")]);
+
+        let results = match_fixed_path_set(
+            candidates(),
+            0,
+            None,
+            "readme",
+            Case::Ignore,
+            MatchKind::Fuzzy,
+            10,
+            &AtomicBool::new(false),
+            PathStyle::Unix,
+        );
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_acronym_match_bonus_counts_filename_humps() {
+        // `H`, `M` and `C` in `HttpMiddlewareController.rs`.
+        assert_eq!(
+            get_acronym_match_bonus("src/HttpMiddlewareController.rs", &[4, 8, 18]),
+            3.0 * ACRONYM_BONUS_PER_HUMP
+        );
+        // `H` and `S` in `HTTPServer.rs`.
+        assert_eq!(
+            get_acronym_match_bonus("HTTPServer.rs", &[0, 4]),
+            2.0 * ACRONYM_BONUS_PER_HUMP
+        );
+        // Contiguous matches inside a word aren't humps.
+        assert_eq!(get_acronym_match_bonus("src/ahmc.rs", &[5, 6, 7]), 0.0);
+        // Humps in directory names don't count.
+        assert_eq!(get_acronym_match_bonus("HttpMiddleware/a.rs", &[0, 4]), 0.0);
+        // `fileFinder` hits the `f` and `F` humps, but the rest of it doesn't spell an acronym.
+        assert_eq!(
+            get_acronym_match_bonus("FileFinder.rs", &(0..10).collect::<Vec<_>>()),
+            0.0
+        );
+    }
+
+    #[gpui::test]
+    async fn test_acronym_bonus_leaves_word_queries_unchanged(executor: BackgroundExecutor) {
+        // Plain word queries score exactly as they did before the acronym bonus, so the
+        // file finder's order for them is unchanged.
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![
+                rel_path("src/file_finder.rs"),
+                rel_path("src/FileFinder.rs"),
+                rel_path("crates/file_finder/src/file_finder.rs"),
+                rel_path("src/FileFinderTests.rs"),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let cancel_flag = AtomicBool::new(false);
+        let mut config = nucleo::Config::DEFAULT;
+        config.set_match_paths();
+        let mut matcher = nucleo::Matcher::new(config);
+        for query in ["filefinder", "FileFinder", "finder", "file finder"] {
+            let matches = match_path_sets(
+                &candidate_sets,
+                query,
+                &None,
+                Case::Ignore,
+                MatchKind::Fuzzy,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await;
+            assert!(!matches.is_empty(), "{query}");
+            let pattern = Query::build(query, Case::Ignore, MatchKind::Fuzzy)
+                .unwrap()
+                .pattern;
+            for path_match in matches {
+                let candidate = path_match.path.as_unix_str();
+                let mut buf = Vec::new();
+                let score = pattern
+                    .score(Utf32Str::new(candidate, &mut buf), &mut matcher)
+                    .unwrap();
+                let expected = score as f64
+                    + get_filename_match_bonus(candidate, &pattern, &mut matcher)
+                    - candidate.len() as f64 * LENGTH_PENALTY;
+                assert_eq!(path_match.score, expected, "{query} against {candidate}");
+            }
+        }
+    }
+
+    #[gpui::test]
+    async fn test_scores_only_matches_rank_like_full_matches(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![
+                rel_path("src/main.rs"),
+                rel_path("src/Main.rs"),
+                rel_path("crates/app/src/main.rs"),
+                rel_path("src/domain/maintenance.rs"),
+                rel_path("src/ahmc.rs"),
+                rel_path("src/html_macros.rs"),
+                rel_path("src/HttpMiddlewareController.rs"),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let cancel_flag = AtomicBool::new(false);
+        // Covers a plain lowercase query, an uppercase query that earns the smart-case
+        // penalty, and an acronym query that earns the hump bonus.
+        for (query, case) in [
+            ("main", Case::Ignore),
+            ("Main", Case::Smart),
+            ("hmc", Case::Ignore),
+            ("HMC", Case::Smart),
+        ] {
+            let full_matches = match_path_sets(
+                &candidate_sets,
+                query,
+                &None,
+                case,
+                MatchKind::Fuzzy,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await;
+            let score_matches = match_path_sets_scores_only(
+                &candidate_sets,
+                query,
+                &None,
+                case,
+                MatchKind::Fuzzy,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await;
+
+            assert!(!full_matches.is_empty(), "{query}");
+            assert!(full_matches.iter().all(|m| !m.positions.is_empty()));
+            assert!(score_matches.iter().all(|m| m.positions.is_empty()));
+            assert_eq!(
+                full_matches
+                    .iter()
+                    .map(|m| (m.path.clone(), m.score))
+                    .collect::<Vec<_>>(),
+                score_matches
+                    .iter()
+                    .map(|m| (m.path.clone(), m.score))
+                    .collect::<Vec<_>>(),
+                "{query}"
+            );
+        }
+    }
+
+    #[gpui::test]
+    async fn test_substring_match_kind_rejects_scattered_matches(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![
+                rel_path("src/main.rs"),
+                rel_path("crates/app/src/main.rs"),
+                rel_path("src/domain/maintenance.rs"),
+                rel_path("scripts/rc/main.rs"),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let cancel_flag = AtomicBool::new(false);
+        let matched_paths = async |match_kind: MatchKind| {
+            let mut paths = match_path_sets(
+                &candidate_sets,
+                "src/main",
+                &None,
+                Case::Ignore,
+                match_kind,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await
+            .into_iter()
+            .map(|m| m.path)
+            .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(
+            matched_paths(MatchKind::Fuzzy).await,
+            vec![
+                rel_path("crates/app/src/main.rs").into_arc(),
+                rel_path("scripts/rc/main.rs").into_arc(),
+                rel_path("src/domain/maintenance.rs").into_arc(),
+                rel_path("src/main.rs").into_arc(),
+            ]
+        );
+        assert_eq!(
+            matched_paths(MatchKind::Substring).await,
+            vec![
+                rel_path("crates/app/src/main.rs").into_arc(),
+                rel_path("src/main.rs").into_arc(),
+            ]
+        );
+        assert!(matched_paths(MatchKind::Exact).await.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_proximity_weight_favors_paths_near_relative_to(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![rel_path("a/config.rs"), rel_path("src/editor/config.rs")],
+            ..TestCandidateSet::default()
+        }];
+        let relative_to = Some(rel_path("src/editor/main.rs").into_arc());
+        let cancel_flag = AtomicBool::new(false);
+        let matched_paths = async |proximity_weight: f64| {
+            match_path_sets(
+                &candidate_sets,
+                "config",
+                &relative_to,
+                Case::Ignore,
+                MatchKind::Fuzzy,
+                proximity_weight,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await
+            .into_iter()
+            .map(|m| m.path)
+            .collect::<Vec<_>>()
+        };
+
+        // Without a weight, the shorter path's smaller length penalty wins.
+        assert_eq!(
+            matched_paths(0.0).await,
+            vec![
+                rel_path("a/config.rs").into_arc(),
+                rel_path("src/editor/config.rs").into_arc(),
+            ]
+        );
+        assert_eq!(
+            matched_paths(0.1).await,
+            vec![
+                rel_path("src/editor/config.rs").into_arc(),
+                rel_path("a/config.rs").into_arc(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_fixed_path_set_honors_cancel_flag() {
+        let paths = [rel_path("src/main.rs"), rel_path("src/lib.rs")];
+        let candidates = || {
+            paths
+                .iter()
+                .map(|path| PathMatchCandidate::new(path, false, None))
+                .collect::<Vec<_>>()
+        };
+
+        let results = match_fixed_path_set(
+            candidates(),
+            0,
+            None,
+            "rs",
+            Case::Ignore,
+            MatchKind::Fuzzy,
+            10,
+            &AtomicBool::new(true),
+            PathStyle::Unix,
+        );
+        assert!(results.is_empty());
+
+        let results = match_fixed_path_set(
+            candidates(),
+            0,
+            None,
+            "rs",
+            Case::Ignore,
+            MatchKind::Fuzzy,
+            10,
+            &AtomicBool::new(false),
+            PathStyle::Unix,
+        );
+        assert_eq!(results.len(), 2);
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_orders_equal_matches_by_path_prefix(
+        executor: BackgroundExecutor,
+    ) {
+        // Both worktrees report the same id and their prefixes are the same length, so the
+        // matches tie on everything but `path_prefix`.
+        let candidate_set = |prefix| TestCandidateSet {
+            paths: vec![rel_path("src/main.rs")],
+            prefix: Some(rel_path(prefix)),
+        };
+        let cancel_flag = AtomicBool::new(false);
+        let matched_prefixes = async |candidate_sets: &[TestCandidateSet]| {
+            match_path_sets(
+                candidate_sets,
+                "main",
+                &None,
+                Case::Ignore,
+                MatchKind::Fuzzy,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await
+            .into_iter()
+            .map(|m| m.path_prefix)
+            .collect::<Vec<_>>()
+        };
+
+        let expected = vec![rel_path("zeta").into_arc(), rel_path("beta").into_arc()];
+        assert_eq!(
+            matched_prefixes(&[candidate_set("beta"), candidate_set("zeta")]).await,
+            expected
+        );
+        assert_eq!(
+            matched_prefixes(&[candidate_set("zeta"), candidate_set("beta")]).await,
+            expected
+        );
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_distance_to_relative_path(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![
+                rel_path("src/main.rs"),
+                rel_path("src/lib.rs"),
+                rel_path("src/a/b.rs"),
+                rel_path("c.rs"),
+            ],
+            ..TestCandidateSet::default()
+        }];
+        let mut distances = match_path_sets(
+            &candidate_sets,
+            "rs",
+            &Some(rel_path("src/main.rs").into_arc()),
+            Case::Ignore,
+            MatchKind::Fuzzy,
+            0.0,
+            10,
+            &AtomicBool::new(false),
+            executor,
+        )
+        .await
+        .into_iter()
+        .map(|m| (m.path, m.distance_to_relative_ancestor))
+        .collect::<Vec<_>>();
+        distances.sort();
+
+        assert_eq!(
+            distances,
+            vec![
+                (rel_path("c.rs").into_arc(), 3),
+                (rel_path("src/a/b.rs").into_arc(), 3),
+                (rel_path("src/lib.rs").into_arc(), 2),
+                (rel_path("src/main.rs").into_arc(), 0),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_match_path_sets_case_modes(executor: BackgroundExecutor) {
+        let candidate_sets = [TestCandidateSet {
+            paths: vec![rel_path("main.rs"), rel_path("Main.rs")],
+            ..TestCandidateSet::default()
+        }];
+        let cancel_flag = AtomicBool::new(false);
+        let matched_paths = async |case: Case| {
+            match_path_sets(
+                &candidate_sets,
+                "Main",
+                &None,
+                case,
+                MatchKind::Fuzzy,
+                0.0,
+                10,
+                &cancel_flag,
+                executor.clone(),
+            )
+            .await
+            .into_iter()
+            .map(|m| m.path)
+            .collect::<Vec<_>>()
+        };
+
+        // Smart case keeps both, ranking the candidate whose case agrees first.
+        assert_eq!(
+            matched_paths(Case::Smart).await,
+            vec![
+                rel_path("Main.rs").into_arc(),
+                rel_path("main.rs").into_arc()
+            ]
+        );
+        let mut ignored = matched_paths(Case::Ignore).await;
+        ignored.sort();
+        assert_eq!(
+            ignored,
+            vec![
+                rel_path("Main.rs").into_arc(),
+                rel_path("main.rs").into_arc()
+            ]
+        );
+        assert_eq!(
+            matched_paths(Case::Exact).await,
+            vec![rel_path("Main.rs").into_arc()]
+        );
+    }
+}
@@ -10,7 +10,7 @@ use gpui::{BackgroundExecutor, SharedString};
 use nucleo::Utf32Str;
 
 use crate::{
-    Cancelled, Case, LengthPenalty, Query, case_penalty, count_case_mismatches,
+    Cancelled, Case, LengthPenalty, MatchKind, Query, case_penalty, count_case_mismatches,
     matcher::{self, LENGTH_PENALTY},
     positions_from_sorted,
 };
@@ -113,7 +113,7 @@ where
         return Vec::new();
     }
 
-    let Some(query) = Query::build(query, case) else {
+    let Some(query) = Query::build(query, case, MatchKind::Fuzzy) else {
         return empty_query_results(candidates, max_results);
     };
 
@@ -179,7 +179,7 @@ where
         return Vec::new();
     }
 
-    let Some(query) = Query::build(query, case) else {
+    let Some(query) = Query::build(query, case, MatchKind::Fuzzy) else {
         return empty_query_results(candidates, max_results);
     };
 
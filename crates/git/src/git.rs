@@ -353,6 +353,22 @@ impl From<Oid> for usize {
     }
 }
 
+/// Returns whether `rev` is a hex object id or a plain ref name.
+///
+/// Revision syntax such as `rev:path`, `rev^`, `rev~2` or `rev@{1}` is rejected, as are
+/// whitespace and control characters, so the value can be safely combined with a path
+/// before being written to `git cat-file --batch`.
+pub fn is_plain_revision(rev: &str) -> bool {
+    !rev.is_empty()
+        && !rev.starts_with('-')
+        && !rev.contains("..")
+        && rev.chars().all(|c| {
+            !c.is_whitespace()
+                && !c.is_control()
+                && !matches!(c, ':' | '^' | '~' | '{' | '?' | '*' | '[' | '\\')
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,6 +397,22 @@ mod tests {
         assert!("a".repeat(SHA1_HEX_LENGTH + 1).parse::<Oid>().is_err());
         assert!("a".repeat(SHA256_HEX_LENGTH - 1).parse::<Oid>().is_err());
     }
+
+    #[test]
+    fn accepts_only_plain_revisions() {
+        assert!(is_plain_revision("abc1234"));
+        assert!(is_plain_revision("HEAD"));
+        assert!(is_plain_revision("feature/blob-mentions"));
+
+        assert!(!is_plain_revision(""));
+        assert!(!is_plain_revision("HEAD:other.rs"));
+        assert!(!is_plain_revision("HEAD^{tree}"));
+        assert!(!is_plain_revision("HEAD~2"));
+        assert!(!is_plain_revision("HEAD\nother"));
+        assert!(!is_plain_revision("main other"));
+        assert!(!is_plain_revision("main..other"));
+        assert!(!is_plain_revision("--batch"));
+    }
 }
 
 #[repr(i32)]
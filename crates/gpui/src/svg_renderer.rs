@@ -1,6 +1,6 @@
 use crate::{
-    AssetSource, DevicePixels, IsZero, RenderImage, Result, SharedString, Size,
-    swap_rgba_pa_to_bgra,
+    AssetSource, Bounds, DevicePixels, IsZero, RenderImage, Result, SharedString, Size, point,
+    size, swap_rgba_pa_to_bgra,
 };
 use image::Frame;
 use resvg::tiny_skia::Pixmap;
@@ -194,6 +194,32 @@ impl SvgRenderer {
         })
     }
 
+    /// Returns the bounding box of the rendered element with the given `id`, as a fraction of
+    /// the SVG's size, so that `(0, 0)` is the top-left corner and `(1, 1)` the bottom-right
+    /// corner of any rendering of it. Returns `None` if no rendered element has that `id`.
+    pub fn element_bounds(
+        &self,
+        bytes: &[u8],
+        id: &str,
+    ) -> Result<Option<Bounds<f32>>, usvg::Error> {
+        let tree = usvg::Tree::from_data(bytes, &self.usvg_options)?;
+        let Some(node) = tree.node_by_id(id) else {
+            return Ok(None);
+        };
+        let svg_size = tree.size();
+        let bounds = node.abs_bounding_box();
+        Ok(Some(Bounds::new(
+            point(
+                bounds.x() / svg_size.width(),
+                bounds.y() / svg_size.height(),
+            ),
+            size(
+                bounds.width() / svg_size.width(),
+                bounds.height() / svg_size.height(),
+            ),
+        )))
+    }
+
     pub(crate) fn render_alpha_mask(
         &self,
         params: &RenderSvgParams,
@@ -355,6 +381,18 @@ mod tests {
             .expect("SVG with mixed-font text should parse");
     }
 
+    #[test]
+    fn test_element_bounds_are_relative_to_svg_size() {
+        let renderer = SvgRenderer::new(Arc::new(()));
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 20 10"><rect id="a" x="5" y="2" width="10" height="5"/></svg>"#;
+
+        assert_eq!(
+            renderer.element_bounds(svg, "a").unwrap(),
+            Some(Bounds::new(point(0.25, 0.2), size(0.5, 0.5)))
+        );
+        assert_eq!(renderer.element_bounds(svg, "missing").unwrap(), None);
+    }
+
     #[test]
     fn test_is_emoji_presentation() {
         let cases = [
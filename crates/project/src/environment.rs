@@ -68,17 +68,21 @@ impl ProjectEnvironment {
 
     /// Returns the inherited CLI environment, if this project was opened from the Zed CLI.
     pub(crate) fn get_cli_environment(&self) -> Option<HashMap<String, String>> {
-        if cfg!(any(test, feature = "test-support")) {
-            return Some(HashMap::default());
-        }
         if let Some(mut env) = self.cli_environment.clone() {
             set_origin_marker(&mut env, EnvironmentOrigin::Cli);
             Some(env)
+        } else if cfg!(any(test, feature = "test-support")) {
+            Some(HashMap::default())
         } else {
             None
         }
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_cli_environment_for_testing(&mut self, env: HashMap<String, String>) {
+        self.cli_environment = Some(env);
+    }
+
     pub fn buffer_environment(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -810,6 +810,7 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_git_diff);
         client.add_entity_request_handler(Self::handle_tree_diff);
         client.add_entity_request_handler(Self::handle_get_blob_content);
+        client.add_entity_request_handler(Self::handle_load_file_at_revision);
         client.add_entity_request_handler(Self::handle_open_unstaged_diff);
         client.add_entity_request_handler(Self::handle_open_uncommitted_diff);
         client.add_entity_message_handler(Self::handle_update_diff_bases);
@@ -2601,10 +2602,18 @@ impl GitStore {
         cx: &App,
     ) -> Option<(Entity<Repository>, RepoPath)> {
         let abs_path = self.worktree_store.read(cx).absolutize(path, cx)?;
+        self.repository_and_path_for_abs_path(&abs_path, cx)
+    }
+
+    pub fn repository_and_path_for_abs_path(
+        &self,
+        abs_path: &Path,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, RepoPath)> {
         self.repositories
             .values()
             .filter_map(|repo| {
-                let repo_path = repo.read(cx).abs_path_to_repo_path(&abs_path)?;
+                let repo_path = repo.read(cx).abs_path_to_repo_path(abs_path)?;
                 Some((repo.clone(), repo_path))
             })
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
@@ -4124,6 +4133,28 @@ impl GitStore {
         Ok(proto::GetBlobContentResponse { content })
     }
 
+    async fn handle_load_file_at_revision(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadFileAtRevision>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadFileAtRevisionResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repo_path = RepoPath::from_proto(&envelope.payload.path)?;
+        anyhow::ensure!(
+            git::is_plain_revision(&envelope.payload.rev),
+            "invalid revision: {:?}",
+            envelope.payload.rev
+        );
+
+        let content = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_file_at_revision(envelope.payload.rev, repo_path)
+            })
+            .await??;
+        Ok(proto::GitLoadFileAtRevisionResponse { content })
+    }
+
     async fn handle_open_unstaged_diff(
         this: Entity<Self>,
         request: TypedEnvelope<proto::OpenUnstagedDiff>,
@@ -6251,6 +6282,45 @@ impl Repository {
         )
     }
 
+    /// Loads the contents of `path` as of `rev`, or `None` if the path doesn't
+    /// exist at that revision.
+    pub fn load_file_at_revision(
+        &mut self,
+        rev: String,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<Option<String>>> {
+        let id = self.id;
+        self.send_job(
+            "load_file_at_revision",
+            None,
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        let revision = format!("{rev}:{}", path.as_unix_str());
+                        Ok(backend
+                            .load_revisions(vec![revision])
+                            .await?
+                            .into_iter()
+                            .next()
+                            .flatten())
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitLoadFileAtRevision {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                rev,
+                                path: path.as_unix_str().to_owned(),
+                            })
+                            .await
+                            .context("sending load file at revision request")?;
+                        Ok(response.content)
+                    }
+                }
+            },
+        )
+    }
+
     pub fn get_graph_data(
         &self,
         log_source: LogSource,
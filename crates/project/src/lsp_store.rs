@@ -7605,9 +7605,8 @@ impl LspStore {
         for row_chunk in applicable_chunks {
             match (
                 existing_inlay_hints
-                    .cached_hints(&row_chunk)
-                    .filter(|_| !lsp_refresh_requested)
-                    .cloned(),
+                    .query(&row_chunk, for_server)
+                    .filter(|_| !lsp_refresh_requested),
                 existing_inlay_hints
                     .fetched_hints(&row_chunk)
                     .as_ref()
@@ -7623,29 +7622,25 @@ impl LspStore {
                 (None, Some(fetched_hints)) => hint_fetch_tasks.push((row_chunk, fetched_hints)),
                 (Some(cached_hints), None) => {
                     for (server_id, cached_hints) in cached_hints {
-                        if for_server.is_none_or(|for_server| for_server == server_id) {
-                            cached_inlay_hints
-                                .get_or_insert_with(HashMap::default)
-                                .entry(row_chunk.row_range())
-                                .or_insert_with(HashMap::default)
-                                .entry(server_id)
-                                .or_insert_with(Vec::new)
-                                .extend(cached_hints);
-                        }
+                        cached_inlay_hints
+                            .get_or_insert_with(HashMap::default)
+                            .entry(row_chunk.row_range())
+                            .or_insert_with(HashMap::default)
+                            .entry(server_id)
+                            .or_insert_with(Vec::new)
+                            .extend(cached_hints);
                     }
                 }
                 (Some(cached_hints), Some(fetched_hints)) => {
                     hint_fetch_tasks.push((row_chunk, fetched_hints));
                     for (server_id, cached_hints) in cached_hints {
-                        if for_server.is_none_or(|for_server| for_server == server_id) {
-                            cached_inlay_hints
-                                .get_or_insert_with(HashMap::default)
-                                .entry(row_chunk.row_range())
-                                .or_insert_with(HashMap::default)
-                                .entry(server_id)
-                                .or_insert_with(Vec::new)
-                                .extend(cached_hints);
-                        }
+                        cached_inlay_hints
+                            .get_or_insert_with(HashMap::default)
+                            .entry(row_chunk.row_range())
+                            .or_insert_with(HashMap::default)
+                            .entry(server_id)
+                            .or_insert_with(Vec::new)
+                            .extend(cached_hints);
                     }
                 }
             }
@@ -7668,15 +7663,22 @@ impl LspStore {
                 // Otherwise apply_fetched_hints (with should_invalidate()=true)
                 // removes all visible hints but only adds back the requesting
                 // server's new hints, permanently losing other servers' hints.
-                let other_servers_cached: CacheInlayHints = if lsp_refresh_requested {
-                    lsp_data
-                        .inlay_hints
-                        .cached_hints(&chunk)
-                        .cloned()
-                        .unwrap_or_default()
-                } else {
-                    HashMap::default()
-                };
+                // When querying a single server, they are still needed to drop
+                // that server's duplicates of hints other servers already reported.
+                let other_servers_cached: CacheInlayHints =
+                    if lsp_refresh_requested || for_server.is_some() {
+                        let mut cached = lsp_data
+                            .inlay_hints
+                            .cached_hints(&chunk)
+                            .cloned()
+                            .unwrap_or_default();
+                        if let Some(for_server) = for_server {
+                            cached.remove(&for_server);
+                        }
+                        cached
+                    } else {
+                        HashMap::default()
+                    };
 
                 let next_hint_id = next_hint_id.clone();
                 let buffer = buffer.clone();
@@ -7692,13 +7694,12 @@ impl LspStore {
                                 lsp_store.update(cx, |lsp_store, cx| {
                                     let lsp_data = lsp_store.latest_lsp_data(&buffer, cx);
                                     let update_cache = lsp_data.buffer_version == query_version;
+                                    let mut result = other_servers_cached;
                                     if new_hints_by_server.is_empty() {
                                         if update_cache {
                                             lsp_data.inlay_hints.invalidate_for_chunk(chunk);
                                         }
-                                        other_servers_cached
                                     } else {
-                                        let mut result = other_servers_cached;
                                         for (server_id, new_hints) in new_hints_by_server {
                                             let new_hints = new_hints
                                                 .into_iter()
@@ -7721,8 +7722,13 @@ impl LspStore {
                                             }
                                             result.insert(server_id, new_hints);
                                         }
-                                        result
                                     }
+                                    inlay_hints::dedup_hints_across_servers(&mut result);
+                                    inlay_hints::order_hints_at_same_position(&mut result);
+                                    if !lsp_refresh_requested && let Some(for_server) = for_server {
+                                        result.retain(|server_id, _| *server_id == for_server);
+                                    }
+                                    result
                                 })
                             })
                             .map_err(Arc::new)
@@ -1,7 +1,7 @@
-use std::{collections::hash_map, ops::Range, sync::Arc};
+use std::{collections::hash_map, mem, ops::Range, sync::Arc};
 
 use anyhow::{Context as _, Result};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use futures::future::Shared;
 use gpui::{App, AppContext as _, AsyncApp, Context, Entity, Task};
 use language::{
@@ -14,8 +14,8 @@ use settings::Settings as _;
 use text::{BufferId, Point};
 
 use crate::{
-    InlayHint, InlayId, LspStore, LspStoreEvent, ResolveState, lsp_command::InlayHints,
-    project_settings::ProjectSettings,
+    InlayHint, InlayHintKind, InlayId, LspStore, LspStoreEvent, ResolveState,
+    lsp_command::InlayHints, project_settings::ProjectSettings,
 };
 
 pub type CacheInlayHints = HashMap<LanguageServerId, Vec<(InlayId, InlayHint)>>;
@@ -104,6 +104,26 @@ impl BufferInlayHints {
         self.hints_by_chunks[chunk.id].as_ref()
     }
 
+    /// Returns the cached hints for the chunk, with hints sharing a position ordered by
+    /// [`order_hints_at_same_position`].
+    ///
+    /// Hints reported at the same position and with the same text by several servers are
+    /// collapsed into one. With `for_server`, only that server's remaining hints are returned,
+    /// so hints already shown for another server are not returned again.
+    pub fn query(
+        &self,
+        chunk: &RowChunk,
+        for_server: Option<LanguageServerId>,
+    ) -> Option<CacheInlayHints> {
+        let mut hints = self.hints_by_chunks.get(chunk.id)?.clone()?;
+        dedup_hints_across_servers(&mut hints);
+        if let Some(for_server) = for_server {
+            hints.retain(|server_id, _| *server_id == for_server);
+        }
+        order_hints_at_same_position(&mut hints);
+        Some(hints)
+    }
+
     pub fn fetched_hints(&mut self, chunk: &RowChunk) -> &mut Option<CacheInlayHintsTask> {
         &mut self.fetches_by_chunks[chunk.id]
     }
@@ -231,6 +251,46 @@ impl BufferInlayHints {
     }
 }
 
+/// Keeps the first hint for every position and text, visiting servers in id order,
+/// so that servers reporting identical hints don't show them twice.
+pub(super) fn dedup_hints_across_servers(hints: &mut CacheInlayHints) {
+    let mut server_ids = hints.keys().copied().collect::<Vec<_>>();
+    server_ids.sort();
+    let mut seen = HashSet::default();
+    for server_id in server_ids {
+        if let Some(server_hints) = hints.get_mut(&server_id) {
+            server_hints.retain(|(_, hint)| seen.insert((hint.position, hint.text().to_string())));
+        }
+    }
+}
+
+/// Groups each server's hints by position, so hints sharing a position are adjacent and
+/// ordered by kind: parameter hints, then type hints, then hints of no kind. Hints of the
+/// same kind at the same position keep the order the server reported them in.
+pub(super) fn order_hints_at_same_position(hints: &mut CacheInlayHints) {
+    fn kind_order(kind: Option<InlayHintKind>) -> u8 {
+        match kind {
+            Some(InlayHintKind::Parameter) => 0,
+            Some(InlayHintKind::Type) => 1,
+            None => 2,
+        }
+    }
+
+    for server_hints in hints.values_mut() {
+        let mut first_index_by_position = HashMap::default();
+        let mut ordered_hints = mem::take(server_hints)
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (id, hint))| {
+                let first_index = *first_index_by_position.entry(hint.position).or_insert(ix);
+                ((first_index, kind_order(hint.kind)), (id, hint))
+            })
+            .collect::<Vec<_>>();
+        ordered_hints.sort_by_key(|(order, _)| *order);
+        *server_hints = ordered_hints.into_iter().map(|(_, hint)| hint).collect();
+    }
+}
+
 impl LspStore {
     pub(super) fn resolve_inlay_hint(
         &self,
@@ -344,3 +404,178 @@ impl LspStore {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InlayHintLabel;
+    use gpui::TestAppContext;
+    use language::Anchor;
+
+    fn hint(position: Anchor, label: &str, kind: Option<InlayHintKind>) -> InlayHint {
+        InlayHint {
+            position,
+            label: InlayHintLabel::String(label.to_string()),
+            kind,
+            padding_left: false,
+            padding_right: false,
+            tooltip: None,
+            resolve_state: ResolveState::Resolved,
+        }
+    }
+
+    #[gpui::test]
+    fn test_query_merges_identical_hints_from_different_servers(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("let x = 1;\n", cx));
+        cx.update(|cx| {
+            let mut inlay_hints = BufferInlayHints::new(&buffer, cx);
+            let chunk = inlay_hints
+                .applicable_chunks(&[Point::new(0, 0)..Point::new(0, 10)])
+                .next()
+                .expect("buffer has a chunk");
+            let hint = hint(
+                buffer.read(cx).anchor_after(Point::new(0, 5)),
+                ": i32",
+                Some(InlayHintKind::Type),
+            );
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(0),
+                vec![(InlayId::Hint(0), hint.clone())],
+            );
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(1),
+                vec![(InlayId::Hint(1), hint.clone())],
+            );
+
+            let hints = inlay_hints
+                .query(&chunk, None)
+                .expect("chunk has cached hints")
+                .into_values()
+                .flatten()
+                .collect::<Vec<_>>();
+            assert_eq!(hints, vec![(InlayId::Hint(0), hint)]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_query_for_refreshed_server_skips_hints_shared_with_other_servers(
+        cx: &mut TestAppContext,
+    ) {
+        let buffer = cx.new(|cx| Buffer::local("let x = 1;\n", cx));
+        cx.update(|cx| {
+            let mut inlay_hints = BufferInlayHints::new(&buffer, cx);
+            let chunk = inlay_hints
+                .applicable_chunks(&[Point::new(0, 0)..Point::new(0, 10)])
+                .next()
+                .expect("buffer has a chunk");
+            let shared_hint = hint(
+                buffer.read(cx).anchor_after(Point::new(0, 5)),
+                ": i32",
+                Some(InlayHintKind::Type),
+            );
+            let own_hint = hint(
+                buffer.read(cx).anchor_after(Point::new(0, 9)),
+                "// one",
+                None,
+            );
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(0),
+                vec![(InlayId::Hint(0), shared_hint.clone())],
+            );
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(1),
+                vec![(InlayId::Hint(1), shared_hint.clone())],
+            );
+
+            assert!(inlay_hints.invalidate_for_server_refresh(LanguageServerId(1), Some(1)));
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(1),
+                vec![
+                    (InlayId::Hint(2), shared_hint.clone()),
+                    (InlayId::Hint(3), own_hint.clone()),
+                ],
+            );
+
+            let refreshed_hints = inlay_hints
+                .query(&chunk, Some(LanguageServerId(1)))
+                .expect("chunk has cached hints");
+            assert_eq!(
+                refreshed_hints.keys().copied().collect::<Vec<_>>(),
+                vec![LanguageServerId(1)]
+            );
+            assert_eq!(
+                refreshed_hints[&LanguageServerId(1)],
+                vec![(InlayId::Hint(3), own_hint.clone())]
+            );
+
+            let mut all_hints = inlay_hints
+                .query(&chunk, None)
+                .expect("chunk has cached hints")
+                .into_values()
+                .flatten()
+                .collect::<Vec<_>>();
+            all_hints.sort_by_key(|(id, _)| *id);
+            assert_eq!(
+                all_hints,
+                vec![
+                    (InlayId::Hint(0), shared_hint),
+                    (InlayId::Hint(3), own_hint)
+                ]
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_query_orders_hints_at_same_position_by_kind(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("foo(x);\n", cx));
+        cx.update(|cx| {
+            let mut inlay_hints = BufferInlayHints::new(&buffer, cx);
+            let chunk = inlay_hints
+                .applicable_chunks(&[Point::new(0, 0)..Point::new(0, 7)])
+                .next()
+                .expect("buffer has a chunk");
+            let at = |column| buffer.read(cx).anchor_after(Point::new(0, column));
+            inlay_hints.insert_new_hints(
+                chunk,
+                LanguageServerId(0),
+                vec![
+                    (
+                        InlayId::Hint(0),
+                        hint(at(4), ": i32", Some(InlayHintKind::Type)),
+                    ),
+                    (InlayId::Hint(1), hint(at(6), "// done", None)),
+                    (
+                        InlayId::Hint(2),
+                        hint(at(4), "value:", Some(InlayHintKind::Parameter)),
+                    ),
+                    (InlayId::Hint(3), hint(at(4), "// note", None)),
+                ],
+            );
+
+            for _ in 0..3 {
+                let ids = inlay_hints
+                    .query(&chunk, None)
+                    .expect("chunk has cached hints")
+                    .remove(&LanguageServerId(0))
+                    .expect("server has hints")
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    ids,
+                    vec![
+                        InlayId::Hint(2),
+                        InlayId::Hint(0),
+                        InlayId::Hint(3),
+                        InlayId::Hint(1),
+                    ]
+                );
+            }
+        });
+    }
+}
@@ -6538,6 +6538,7 @@ impl<'a> Iterator for PathMatchCandidateSetIter<'a> {
                 is_dir: entry.kind.is_dir(),
                 path: &entry.path,
                 char_bag: entry.char_bag,
+                alt_names: &[],
             })
     }
 }
@@ -1,11 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::HashMap;
-use gpui::{App, AppContext as _, Context, Entity, Task, WeakEntity};
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, SharedString, Task, WeakEntity};
 
 use async_channel::bounded;
 use futures::{FutureExt, future::Shared};
 use itertools::Itertools as _;
-use language::LanguageName;
+use language::{LanguageName, LanguageRegistry, Toolchain};
 use remote::{Interactive, RemoteClient};
 use settings::{Settings, SettingsLocation};
 use std::{
@@ -28,6 +28,28 @@ pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
 }
 
+/// Options for [`Project::create_terminal_task_with_options`] and
+/// [`Project::create_terminal_shell_with_options`].
+#[derive(Clone, Debug)]
+pub struct TerminalOptions {
+    /// Whether the terminal starts from the environment inherited from the CLI.
+    /// When false, it starts from an empty environment, and only the terminal
+    /// settings env (and task env) are applied.
+    pub inherit_cli_env: bool,
+    /// Labels the terminal's tab instead of the task label or the title reported
+    /// by the shell.
+    pub title: Option<SharedString>,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            inherit_cli_env: true,
+            title: None,
+        }
+    }
+}
+
 impl Project {
     pub fn active_entry_directory(&self, cx: &App) -> Option<PathBuf> {
         let entry_id = self.active_entry()?;
@@ -65,6 +87,15 @@ impl Project {
         &mut self,
         spawn_task: SpawnInTerminal,
         cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Terminal>>> {
+        self.create_terminal_task_with_options(spawn_task, TerminalOptions::default(), cx)
+    }
+
+    pub fn create_terminal_task_with_options(
+        &mut self,
+        spawn_task: SpawnInTerminal,
+        options: TerminalOptions,
+        cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
         let is_via_remote = self.remote_client.is_some();
 
@@ -80,16 +111,8 @@ impl Project {
             self.active_project_directory(cx)
         };
 
-        let mut settings_location = None;
-        if let Some(path) = path.as_ref()
-            && let Some((worktree, _)) = self.find_worktree(path, cx)
-        {
-            settings_location = Some(SettingsLocation {
-                worktree_id: worktree.read(cx).id(),
-                path: RelPath::empty(),
-            });
-        }
-        let settings = TerminalSettings::get(settings_location, cx).clone();
+        let settings =
+            TerminalSettings::get(self.terminal_settings_location(path.as_deref(), cx), cx).clone();
         let detect_venv = settings.detect_venv.as_option().is_some();
 
         let (completion_tx, completion_rx) = bounded(1);
@@ -112,136 +135,56 @@ impl Project {
         let shell_kind = ShellKind::new(&shell, path_style.is_windows());
 
         // Prepare a task for resolving the environment
-        let env_task =
-            self.resolve_directory_environment(&shell, path.clone(), remote_client.clone(), cx);
-
-        // Scope the toolchain lookup to the worktree the terminal is being
-        // spawned in. Previously this iterated the active editor's worktree
-        // and then every visible worktree, so a Python toolchain persisted
-        // for worktree A would leak into a terminal opened in worktree B and
-        // inject (e.g.) `conda activate base` into a shell that has no
-        // business with conda.
-        let project_path_contexts: Vec<ProjectPath> = path
-            .as_ref()
-            .and_then(|p| self.find_worktree(p, cx))
-            .map(|(worktree, relative_path)| ProjectPath {
-                worktree_id: worktree.read(cx).id(),
-                path: relative_path,
-            })
-            .into_iter()
-            .collect();
-        let toolchains = project_path_contexts
-            .into_iter()
-            .filter(|_| detect_venv)
-            .map(|p| self.active_toolchain(p, LanguageName::new_static("Python"), cx))
-            .collect::<Vec<_>>();
+        let env_task = self.resolve_directory_environment(
+            &shell,
+            path.clone(),
+            remote_client.clone(),
+            options.inherit_cli_env,
+            cx,
+        );
+
+        let toolchains = self.python_toolchains_for_terminal(path.as_deref(), detect_venv, cx);
         let lang_registry = self.languages.clone();
         cx.spawn(async move |project, cx| {
             let mut env = env_task.await.unwrap_or_default();
             env.extend(settings.env);
 
-            let activation_script = maybe!(async {
-                for toolchain in toolchains {
-                    let Some(toolchain) = toolchain.await else {
-                        continue;
-                    };
-                    let language = lang_registry
-                        .language_for_name(&toolchain.language_name.0)
-                        .await
-                        .ok();
-                    let lister = language?.toolchain_lister()?;
-                    let future =
-                        cx.update(|cx| lister.activation_script(&toolchain, shell_kind, cx));
-                    return Some(future.await);
-                }
-                None
-            })
-            .await
-            .unwrap_or_default();
+            let activation_script =
+                resolve_activation_script(toolchains, lang_registry, shell_kind, cx).await;
 
+            let spawn_context = terminal_spawn_context(
+                spawn_task.command.as_deref().unwrap_or(&shell),
+                is_via_remote,
+                path.as_deref(),
+            );
+            let remote_spawn_context = spawn_context.clone();
             let builder = project
                 .update(cx, move |_, cx| {
-                    let format_to_run = |spawn_task: &SpawnInTerminal| {
-                        format_task_for_activation(
-                            spawn_task,
+                    let command = if activation_script.is_empty() {
+                        spawn_task.command.map(|command| (command, spawn_task.args))
+                    } else {
+                        let separator = shell_kind.sequential_commands_separator();
+                        let activation_script = activation_script.join(&format!("{separator} "));
+                        let to_run = format_task_for_activation(
+                            &spawn_task,
                             shell_kind,
                             &shell,
                             path_style.is_windows(),
-                        )
-                    };
-
-                    let (shell, env) = {
-                        let to_run =
-                            (!activation_script.is_empty()).then(|| format_to_run(&spawn_task));
-                        env.extend(spawn_task.env);
-                        match remote_client {
-                            Some(remote_client) => match activation_script.clone() {
-                                activation_script if !activation_script.is_empty() => {
-                                    let separator = shell_kind.sequential_commands_separator();
-                                    let activation_script =
-                                        activation_script.join(&format!("{separator} "));
-                                    let to_run = to_run.expect("activation command was formatted");
-
-                                    let arg = format!("{activation_script}{separator} {to_run}");
-                                    let args = shell_kind.args_for_shell(true, arg);
-                                    let shell = remote_client
-                                        .read(cx)
-                                        .shell()
-                                        .unwrap_or_else(get_default_system_shell);
-
-                                    create_remote_shell(
-                                        Some((&shell, &args)),
-                                        env,
-                                        path,
-                                        remote_client,
-                                        cx,
-                                    )?
-                                }
-                                _ => create_remote_shell(
-                                    spawn_task
-                                        .command
-                                        .as_ref()
-                                        .map(|command| (command, &spawn_task.args)),
-                                    env,
-                                    path,
-                                    remote_client,
-                                    cx,
-                                )?,
-                            },
-                            None => match activation_script.clone() {
-                                activation_script if !activation_script.is_empty() => {
-                                    let separator = shell_kind.sequential_commands_separator();
-                                    let activation_script =
-                                        activation_script.join(&format!("{separator} "));
-                                    let to_run = to_run.expect("activation command was formatted");
-
-                                    let arg = format!("{activation_script}{separator} {to_run}");
-                                    let args = shell_kind.args_for_shell(true, arg);
-
-                                    (
-                                        Shell::WithArguments {
-                                            program: shell,
-                                            args,
-                                            title_override: None,
-                                        },
-                                        env,
-                                    )
-                                }
-                                _ => (
-                                    if let Some(program) = spawn_task.command {
-                                        Shell::WithArguments {
-                                            program,
-                                            args: spawn_task.args,
-                                            title_override: None,
-                                        }
-                                    } else {
-                                        Shell::System
-                                    },
-                                    env,
-                                ),
-                            },
-                        }
+                        );
+                        let arg = format!("{activation_script}{separator} {to_run}");
+                        Some((shell, shell_kind.args_for_shell(true, arg)))
                     };
+                    env.extend(spawn_task.env);
+                    let (shell, env) = resolve_shell_and_env(
+                        command,
+                        Shell::System,
+                        options.title.map(|title| title.to_string()),
+                        env,
+                        path,
+                        remote_client,
+                        cx,
+                    )
+                    .context(remote_spawn_context)?;
                     anyhow::Ok(TerminalBuilder::new(
                         local_path.map(|path| path.to_path_buf()),
                         task_state,
@@ -260,29 +203,10 @@ impl Project {
                         path_style,
                     ))
                 })??
-                .await?;
+                .await
+                .context(spawn_context)?;
             project.update(cx, move |this, cx| {
-                let terminal_handle = cx.new(|cx| builder.subscribe(cx));
-
-                this.terminals
-                    .local_handles
-                    .push(terminal_handle.downgrade());
-
-                let id = terminal_handle.entity_id();
-                cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
-                    let handles = &mut project.terminals.local_handles;
-
-                    if let Some(index) = handles
-                        .iter()
-                        .position(|terminal| terminal.entity_id() == id)
-                    {
-                        handles.remove(index);
-                        cx.notify();
-                    }
-                })
-                .detach();
-
-                terminal_handle
+                this.register_local_terminal(builder, cx)
             })
         })
     }
@@ -292,7 +216,16 @@ impl Project {
         cwd: Option<PathBuf>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
-        self.create_terminal_shell_internal(cwd, false, cx)
+        self.create_terminal_shell_with_options(cwd, TerminalOptions::default(), cx)
+    }
+
+    pub fn create_terminal_shell_with_options(
+        &mut self,
+        cwd: Option<PathBuf>,
+        options: TerminalOptions,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Terminal>>> {
+        self.create_terminal_shell_internal(cwd, false, options, cx)
     }
 
     /// Creates a local terminal even if the project is remote.
@@ -309,7 +242,7 @@ impl Project {
             // Local project: use project directory like normal terminals
             self.active_project_directory(cx).map(|p| p.to_path_buf())
         };
-        self.create_terminal_shell_internal(working_directory, true, cx)
+        self.create_terminal_shell_internal(working_directory, true, TerminalOptions::default(), cx)
     }
 
     /// Internal method for creating terminal shells.
@@ -319,41 +252,18 @@ impl Project {
         &mut self,
         cwd: Option<PathBuf>,
         force_local: bool,
+        options: TerminalOptions,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
         let path = cwd.map(|p| Arc::from(&*p));
         let is_via_remote = !force_local && self.remote_client.is_some();
 
-        let mut settings_location = None;
-        if let Some(path) = path.as_ref()
-            && let Some((worktree, _)) = self.find_worktree(path, cx)
-        {
-            settings_location = Some(SettingsLocation {
-                worktree_id: worktree.read(cx).id(),
-                path: RelPath::empty(),
-            });
-        }
-        let settings = TerminalSettings::get(settings_location, cx).clone();
+        let settings =
+            TerminalSettings::get(self.terminal_settings_location(path.as_deref(), cx), cx).clone();
         let detect_venv = settings.detect_venv.as_option().is_some();
         let local_path = if is_via_remote { None } else { path.clone() };
 
-        // See create_terminal_task: scope the toolchain lookup to the
-        // worktree the terminal is opened in, not the active editor's
-        // worktree or other visible worktrees.
-        let project_path_contexts: Vec<ProjectPath> = path
-            .as_ref()
-            .and_then(|p| self.find_worktree(p, cx))
-            .map(|(worktree, relative_path)| ProjectPath {
-                worktree_id: worktree.read(cx).id(),
-                path: relative_path,
-            })
-            .into_iter()
-            .collect();
-        let toolchains = project_path_contexts
-            .into_iter()
-            .filter(|_| detect_venv)
-            .map(|p| self.active_toolchain(p, LanguageName::new_static("Python"), cx))
-            .collect::<Vec<_>>();
+        let toolchains = self.python_toolchains_for_terminal(path.as_deref(), detect_venv, cx);
         let remote_client = if force_local {
             None
         } else {
@@ -374,8 +284,13 @@ impl Project {
         let path_style = self.path_style(cx);
 
         // Prepare a task for resolving the environment
-        let env_task =
-            self.resolve_directory_environment(&env_shell, path.clone(), remote_client.clone(), cx);
+        let env_task = self.resolve_directory_environment(
+            &env_shell,
+            path.clone(),
+            remote_client.clone(),
+            options.inherit_cli_env,
+            cx,
+        );
 
         let lang_registry = self.languages.clone();
         cx.spawn(async move |project, cx| {
@@ -383,35 +298,23 @@ impl Project {
             let mut env = env_task.await.unwrap_or_default();
             env.extend(settings.env);
 
-            let activation_script = maybe!(async {
-                for toolchain in toolchains {
-                    let Some(toolchain) = toolchain.await else {
-                        continue;
-                    };
-                    let language = lang_registry
-                        .language_for_name(&toolchain.language_name.0)
-                        .await
-                        .ok();
-                    let lister = language?.toolchain_lister()?;
-                    let future =
-                        cx.update(|cx| lister.activation_script(&toolchain, shell_kind, cx));
-                    return Some(future.await);
-                }
-                None
-            })
-            .await
-            .unwrap_or_default();
+            let activation_script =
+                resolve_activation_script(toolchains, lang_registry, shell_kind, cx).await;
 
+            let spawn_context = terminal_spawn_context(&shell, is_via_remote, path.as_deref());
+            let remote_spawn_context = spawn_context.clone();
             let builder = project
                 .update(cx, move |_, cx| {
-                    let (shell, env) = {
-                        match remote_client {
-                            Some(remote_client) => {
-                                create_remote_shell(None, env, path, remote_client, cx)?
-                            }
-                            None => (settings.shell, env),
-                        }
-                    };
+                    let (shell, env) = resolve_shell_and_env(
+                        None,
+                        settings.shell,
+                        options.title.map(|title| title.to_string()),
+                        env,
+                        path,
+                        remote_client,
+                        cx,
+                    )
+                    .context(remote_spawn_context)?;
                     anyhow::Ok(TerminalBuilder::new(
                         local_path.map(|path| path.to_path_buf()),
                         None,
@@ -430,29 +333,10 @@ impl Project {
                         path_style,
                     ))
                 })??
-                .await?;
+                .await
+                .context(spawn_context)?;
             project.update(cx, move |this, cx| {
-                let terminal_handle = cx.new(|cx| builder.subscribe(cx));
-
-                this.terminals
-                    .local_handles
-                    .push(terminal_handle.downgrade());
-
-                let id = terminal_handle.entity_id();
-                cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
-                    let handles = &mut project.terminals.local_handles;
-
-                    if let Some(index) = handles
-                        .iter()
-                        .position(|terminal| terminal.entity_id() == id)
-                    {
-                        handles.remove(index);
-                        cx.notify();
-                    }
-                })
-                .detach();
-
-                terminal_handle
+                this.register_local_terminal(builder, cx)
             })
         })
     }
@@ -478,28 +362,7 @@ impl Project {
         cx.spawn(async |project, cx| {
             let terminal = builder.await?;
             project.update(cx, |project, cx| {
-                let terminal_handle = cx.new(|cx| terminal.subscribe(cx));
-
-                project
-                    .terminals
-                    .local_handles
-                    .push(terminal_handle.downgrade());
-
-                let id = terminal_handle.entity_id();
-                cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
-                    let handles = &mut project.terminals.local_handles;
-
-                    if let Some(index) = handles
-                        .iter()
-                        .position(|terminal| terminal.entity_id() == id)
-                    {
-                        handles.remove(index);
-                        cx.notify();
-                    }
-                })
-                .detach();
-
-                terminal_handle
+                project.register_local_terminal(terminal, cx)
             })
         })
     }
@@ -509,16 +372,72 @@ impl Project {
         path: &'a Option<PathBuf>,
         cx: &'a App,
     ) -> &'a TerminalSettings {
-        let mut settings_location = None;
-        if let Some(path) = path.as_ref()
-            && let Some((worktree, _)) = self.find_worktree(path, cx)
-        {
-            settings_location = Some(SettingsLocation {
-                worktree_id: worktree.read(cx).id(),
-                path: RelPath::empty(),
-            });
+        TerminalSettings::get(self.terminal_settings_location(path.as_deref(), cx), cx)
+    }
+
+    fn terminal_settings_location(
+        &self,
+        path: Option<&Path>,
+        cx: &App,
+    ) -> Option<SettingsLocation<'static>> {
+        let (worktree, _) = self.find_worktree(path?, cx)?;
+        Some(SettingsLocation {
+            worktree_id: worktree.read(cx).id(),
+            path: RelPath::empty(),
+        })
+    }
+
+    // Scope the toolchain lookup to the worktree the terminal is being
+    // spawned in. Previously this iterated the active editor's worktree
+    // and then every visible worktree, so a Python toolchain persisted
+    // for worktree A would leak into a terminal opened in worktree B and
+    // inject (e.g.) `conda activate base` into a shell that has no
+    // business with conda.
+    fn python_toolchains_for_terminal(
+        &self,
+        path: Option<&Path>,
+        detect_venv: bool,
+        cx: &App,
+    ) -> Vec<Task<Option<Toolchain>>> {
+        if !detect_venv {
+            return Vec::new();
         }
-        TerminalSettings::get(settings_location, cx)
+        path.and_then(|path| self.find_worktree(path, cx))
+            .map(|(worktree, relative_path)| ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path: relative_path,
+            })
+            .into_iter()
+            .map(|path| self.active_toolchain(path, LanguageName::new_static("Python"), cx))
+            .collect()
+    }
+
+    fn register_local_terminal(
+        &mut self,
+        builder: TerminalBuilder,
+        cx: &mut Context<Self>,
+    ) -> Entity<Terminal> {
+        let terminal_handle = cx.new(|cx| builder.subscribe(cx));
+
+        self.terminals
+            .local_handles
+            .push(terminal_handle.downgrade());
+
+        let id = terminal_handle.entity_id();
+        cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
+            let handles = &mut project.terminals.local_handles;
+
+            if let Some(index) = handles
+                .iter()
+                .position(|terminal| terminal.entity_id() == id)
+            {
+                handles.remove(index);
+                cx.notify();
+            }
+        })
+        .detach();
+
+        terminal_handle
     }
 
     pub fn exec_in_shell(
@@ -526,9 +445,22 @@ impl Project {
         command: String,
         cx: &mut Context<Self>,
     ) -> Task<Result<smol::process::Command>> {
-        let path = self.first_project_directory(cx);
+        let cwd = self
+            .active_project_directory(cx)
+            .map(|path| path.to_path_buf());
+        self.exec_in_shell_in(command, cwd, cx)
+    }
+
+    /// Like [`Self::exec_in_shell`], but runs the command in `cwd` instead of the
+    /// active worktree's root.
+    pub fn exec_in_shell_in(
+        &self,
+        command: String,
+        cwd: Option<PathBuf>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<smol::process::Command>> {
         let remote_client = self.remote_client.clone();
-        let settings = self.terminal_settings(&path, cx).clone();
+        let settings = self.terminal_settings(&cwd, cx).clone();
         let shell = remote_client
             .as_ref()
             .and_then(|remote_client| remote_client.read(cx).shell())
@@ -540,8 +472,9 @@ impl Project {
 
         let env_task = self.resolve_directory_environment(
             &shell.program(),
-            path.as_ref().map(|p| Arc::from(&**p)),
+            cwd.as_ref().map(|p| Arc::from(&**p)),
             remote_client.clone(),
+            true,
             cx,
         );
 
@@ -556,7 +489,7 @@ impl Project {
                             Some(command),
                             &args,
                             &env,
-                            None,
+                            cwd.map(|path| path.display().to_string()),
                             None,
                             Interactive::Yes,
                         )?;
@@ -569,8 +502,8 @@ impl Project {
                         let mut command = new_std_command(command);
                         command.args(args);
                         command.envs(env);
-                        if let Some(path) = path {
-                            command.current_dir(path);
+                        if let Some(cwd) = cwd {
+                            command.current_dir(cwd);
                         }
                         Ok(command)
                     }
@@ -587,13 +520,19 @@ impl Project {
         &self.terminals.local_handles
     }
 
+    /// Resolves the environment a terminal in `path` starts from. Without
+    /// `inherit_cli_env`, terminals start from an empty environment instead.
     fn resolve_directory_environment(
         &self,
         shell: &str,
         path: Option<Arc<Path>>,
         remote_client: Option<Entity<RemoteClient>>,
+        inherit_cli_env: bool,
         cx: &mut App,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
+        if !inherit_cli_env {
+            return Task::ready(Some(HashMap::default())).shared();
+        }
         if let Some(path) = &path {
             let shell = Shell::Program(shell.to_string());
             self.environment
@@ -612,8 +551,99 @@ impl Project {
     }
 }
 
+async fn resolve_activation_script(
+    toolchains: Vec<Task<Option<Toolchain>>>,
+    language_registry: Arc<LanguageRegistry>,
+    shell_kind: ShellKind,
+    cx: &mut AsyncApp,
+) -> Vec<String> {
+    maybe!(async {
+        for toolchain in toolchains {
+            let Some(toolchain) = toolchain.await else {
+                continue;
+            };
+            let language = language_registry
+                .language_for_name(&toolchain.language_name.0)
+                .await
+                .ok();
+            let lister = language?.toolchain_lister()?;
+            let future = cx.update(|cx| lister.activation_script(&toolchain, shell_kind, cx));
+            return Some(future.await);
+        }
+        None
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Describes a terminal spawn for errors, so a failure can be told apart by the
+/// program being run, SSH wrapping, and working directory.
+fn terminal_spawn_context(program: &str, is_via_remote: bool, cwd: Option<&Path>) -> String {
+    let kind = if is_via_remote {
+        "SSH terminal"
+    } else {
+        "local terminal"
+    };
+    let cwd = cwd.map_or_else(
+        || "the default directory".to_string(),
+        |cwd| cwd.display().to_string(),
+    );
+    format!("spawning {kind} running `{program}` in {cwd}")
+}
+
+/// Picks the shell a terminal runs: `command` when given, `default_shell` otherwise,
+/// labeled with `title_override` when given.
+/// Remote terminals get the shell and `env` wrapped into an SSH command.
+fn resolve_shell_and_env(
+    command: Option<(String, Vec<String>)>,
+    default_shell: Shell,
+    title_override: Option<String>,
+    env: HashMap<String, String>,
+    working_directory: Option<Arc<Path>>,
+    remote_client: Option<Entity<RemoteClient>>,
+    cx: &mut App,
+) -> Result<(Shell, HashMap<String, String>)> {
+    match remote_client {
+        Some(remote_client) => create_remote_shell(
+            command.as_ref().map(|(program, args)| (program, args)),
+            title_override,
+            env,
+            working_directory,
+            remote_client,
+            cx,
+        ),
+        None => {
+            let shell = match (command, default_shell) {
+                (Some((program, args)), _) => Shell::WithArguments {
+                    program,
+                    args,
+                    title_override,
+                },
+                (None, shell) if title_override.is_none() => shell,
+                (None, Shell::System) => Shell::WithArguments {
+                    program: get_system_shell(),
+                    args: Vec::new(),
+                    title_override,
+                },
+                (None, Shell::Program(program)) => Shell::WithArguments {
+                    program,
+                    args: Vec::new(),
+                    title_override,
+                },
+                (None, Shell::WithArguments { program, args, .. }) => Shell::WithArguments {
+                    program,
+                    args,
+                    title_override,
+                },
+            };
+            Ok((shell, env))
+        }
+    }
+}
+
 fn create_remote_shell(
     spawn_command: Option<(&String, &Vec<String>)>,
+    title_override: Option<String>,
     mut env: HashMap<String, String>,
     working_directory: Option<Arc<Path>>,
     remote_client: Entity<RemoteClient>,
@@ -636,13 +666,19 @@ fn create_remote_shell(
     )?;
 
     log::debug!("Connecting to a remote server: {:?}", command.program);
-    let host = remote_client.read(cx).connection_options().display_name();
+    // Tasks are labeled after themselves, plain shells after the host they run on.
+    let title_override = title_override.or_else(|| {
+        spawn_command.is_none().then(|| {
+            let host = remote_client.read(cx).connection_options().display_name();
+            format!("{} — Terminal", host)
+        })
+    });
 
     Ok((
         Shell::WithArguments {
             program: command.program,
             args: command.args,
-            title_override: Some(format!("{} — Terminal", host)),
+            title_override,
         },
         command.env,
     ))
@@ -797,4 +833,186 @@ mod tests {
             "&cargo test 'some test'"
         );
     }
+
+    #[test]
+    fn terminal_spawn_context_mentions_cwd_and_ssh() {
+        let error = anyhow::anyhow!("connection refused").context(terminal_spawn_context(
+            "bash",
+            true,
+            Some(Path::new("/srv/app")),
+        ));
+        let message = format!("{error:#}");
+        assert!(message.contains("SSH terminal"), "{message}");
+        assert!(message.contains("/srv/app"), "{message}");
+        assert!(message.contains("connection refused"), "{message}");
+
+        assert_eq!(
+            terminal_spawn_context("zsh", false, None),
+            "spawning local terminal running `zsh` in the default directory"
+        );
+    }
+
+    #[gpui::test]
+    fn resolve_shell_and_env_for_local_terminals(cx: &mut gpui::TestAppContext) {
+        let env = HashMap::from_iter([("FOO".to_string(), "1".to_string())]);
+
+        cx.update(|cx| {
+            let (task_shell, task_env) = resolve_shell_and_env(
+                Some(("cargo".to_string(), vec!["test".to_string()])),
+                Shell::System,
+                Some("Build".to_string()),
+                env.clone(),
+                None,
+                None,
+                cx,
+            )
+            .unwrap();
+            assert_eq!(
+                task_shell,
+                Shell::WithArguments {
+                    program: "cargo".to_string(),
+                    args: vec!["test".to_string()],
+                    title_override: Some("Build".to_string()),
+                }
+            );
+            assert_eq!(task_env, env);
+
+            let (plain_shell, plain_env) = resolve_shell_and_env(
+                None,
+                Shell::Program("fish".to_string()),
+                None,
+                env.clone(),
+                None,
+                None,
+                cx,
+            )
+            .unwrap();
+            assert_eq!(plain_shell, Shell::Program("fish".to_string()));
+            assert_eq!(plain_env, env);
+
+            let (titled_shell, _) = resolve_shell_and_env(
+                None,
+                Shell::Program("fish".to_string()),
+                Some("Dev server".to_string()),
+                env.clone(),
+                None,
+                None,
+                cx,
+            )
+            .unwrap();
+            assert_eq!(
+                titled_shell,
+                Shell::WithArguments {
+                    program: "fish".to_string(),
+                    args: Vec::new(),
+                    title_override: Some("Dev server".to_string()),
+                }
+            );
+
+            let (titled_system_shell, _) = resolve_shell_and_env(
+                None,
+                Shell::System,
+                Some("Dev server".to_string()),
+                env.clone(),
+                None,
+                None,
+                cx,
+            )
+            .unwrap();
+            assert_eq!(
+                titled_system_shell,
+                Shell::WithArguments {
+                    program: get_system_shell(),
+                    args: Vec::new(),
+                    title_override: Some("Dev server".to_string()),
+                }
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn resolve_shell_and_env_wraps_remote_terminals(
+        cx: &mut gpui::TestAppContext,
+        server_cx: &mut gpui::TestAppContext,
+    ) {
+        cx.update(|cx| release_channel::init(semver::Version::new(0, 0, 0), cx));
+        server_cx.update(|cx| release_channel::init(semver::Version::new(0, 0, 0), cx));
+        let (opts, server_session, connect_guard) = RemoteClient::fake_server(cx, server_cx);
+        let ping_handler = server_cx.new(|_| ());
+        server_session.add_request_handler::<rpc::proto::Ping, _, _, _>(
+            ping_handler.downgrade(),
+            |_entity, _envelope, _cx| async { Ok(rpc::proto::Ack {}) },
+        );
+        drop(connect_guard);
+        let remote_client = RemoteClient::connect_mock(opts, cx).await;
+
+        let env = HashMap::from_iter([("FOO".to_string(), "1".to_string())]);
+        cx.update(|cx| {
+            let (task_shell, task_env) = resolve_shell_and_env(
+                Some(("cargo".to_string(), vec!["test".to_string()])),
+                Shell::System,
+                Some("Build".to_string()),
+                env.clone(),
+                Some(Arc::from(Path::new("/srv/app"))),
+                Some(remote_client.clone()),
+                cx,
+            )
+            .unwrap();
+            let Shell::WithArguments {
+                program,
+                args,
+                title_override,
+            } = task_shell
+            else {
+                panic!("expected the task to be wrapped for SSH, got {task_shell:?}");
+            };
+            assert_eq!(program, "mock");
+            assert_eq!(args, vec!["cargo".to_string(), "test".to_string()]);
+            assert_eq!(title_override.as_deref(), Some("Build"));
+            assert_eq!(task_env.get("FOO").map(String::as_str), Some("1"));
+
+            let (plain_shell, plain_env) = resolve_shell_and_env(
+                None,
+                Shell::Program("fish".to_string()),
+                None,
+                env.clone(),
+                None,
+                Some(remote_client.clone()),
+                cx,
+            )
+            .unwrap();
+            let Shell::WithArguments {
+                program,
+                args,
+                title_override,
+            } = plain_shell
+            else {
+                panic!("expected the shell to be wrapped for SSH, got {plain_shell:?}");
+            };
+            assert_eq!(program, "mock");
+            assert_eq!(args, vec!["sh".to_string()]);
+            assert!(
+                title_override
+                    .as_deref()
+                    .is_some_and(|title| title.ends_with(" — Terminal")),
+                "{title_override:?}"
+            );
+            assert_eq!(plain_env.get("FOO").map(String::as_str), Some("1"));
+
+            let (titled_shell, _) = resolve_shell_and_env(
+                None,
+                Shell::Program("fish".to_string()),
+                Some("Dev server".to_string()),
+                env.clone(),
+                None,
+                Some(remote_client.clone()),
+                cx,
+            )
+            .unwrap();
+            let Shell::WithArguments { title_override, .. } = titled_shell else {
+                panic!("expected the shell to be wrapped for SSH, got {titled_shell:?}");
+            };
+            assert_eq!(title_override.as_deref(), Some("Dev server"));
+        });
+    }
 }
@@ -187,6 +187,113 @@ async fn test_default_session_work_dirs_falls_back_to_home_for_empty_project(
     assert_eq!(ordered_paths, vec![paths::home_dir().to_path_buf()]);
 }
 
+#[gpui::test]
+async fn test_active_project_directory_follows_active_worktree(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "first": { "a.rs": "" },
+            "second": { "b.rs": "" }
+        }),
+    )
+    .await;
+
+    let project = Project::test(
+        fs,
+        [
+            Path::new(path!("/root/first")),
+            Path::new(path!("/root/second")),
+        ],
+        cx,
+    )
+    .await;
+
+    let active_directory = |cx: &mut gpui::TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .active_project_directory(cx)
+                .map(|path| path.to_path_buf())
+        })
+    };
+    assert_eq!(
+        active_directory(cx),
+        Some(PathBuf::from(path!("/root/first")))
+    );
+
+    let second_worktree_id = project.read_with(cx, |project, cx| {
+        project
+            .worktree_for_root_name("second", cx)
+            .unwrap()
+            .read(cx)
+            .id()
+    });
+    project.update(cx, |project, cx| {
+        project.set_active_path(
+            Some(ProjectPath {
+                worktree_id: second_worktree_id,
+                path: rel_path("b.rs").into(),
+            }),
+            cx,
+        )
+    });
+    assert_eq!(
+        active_directory(cx),
+        Some(PathBuf::from(path!("/root/second")))
+    );
+}
+
+#[cfg(not(windows))]
+#[gpui::test]
+async fn test_exec_in_shell_runs_in_requested_directory(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.executor().allow_parking();
+
+    let dir = TempTree::new(json!({
+        "first": { "a.rs": "" },
+        "second": { "b.rs": "" }
+    }));
+    let root = dir.path().canonicalize().unwrap();
+    let project = Project::test(
+        Arc::new(RealFs::new(None, cx.executor())),
+        [root.join("first").as_ref(), root.join("second").as_ref()],
+        cx,
+    )
+    .await;
+
+    async fn current_dir(command: Task<Result<smol::process::Command>>) -> PathBuf {
+        let output = command.await.unwrap().output().await.unwrap();
+        assert!(output.status.success(), "{output:?}");
+        PathBuf::from(String::from_utf8(output.stdout).unwrap().trim())
+    }
+
+    let second_worktree_id = project.read_with(cx, |project, cx| {
+        project
+            .worktree_for_root_name("second", cx)
+            .unwrap()
+            .read(cx)
+            .id()
+    });
+    project.update(cx, |project, cx| {
+        project.set_active_path(
+            Some(ProjectPath {
+                worktree_id: second_worktree_id,
+                path: rel_path("b.rs").into(),
+            }),
+            cx,
+        )
+    });
+    let command = project.update(cx, |project, cx| project.exec_in_shell("pwd".into(), cx));
+    assert_eq!(current_dir(command).await, root.join("second"));
+
+    let command = project.update(cx, |project, cx| {
+        project.exec_in_shell_in("pwd".into(), Some(root.join("first")), cx)
+    });
+    assert_eq!(current_dir(command).await, root.join("first"));
+}
+
 // NOTE:
 // While POSIX symbolic links are somewhat supported on Windows, they are an opt in by the user, and thus
 // we assume that they are not supported out of the box.
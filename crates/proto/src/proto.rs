@@ -354,6 +354,8 @@ messages!(
     (GetTreeDiffResponse, Background),
     (GetBlobContent, Background),
     (GetBlobContentResponse, Background),
+    (GitLoadFileAtRevision, Background),
+    (GitLoadFileAtRevisionResponse, Background),
     (GitClone, Background),
     (GitCloneResponse, Background),
     (ToggleLspLogs, Background),
@@ -593,6 +595,7 @@ request_messages!(
     (PullWorkspaceDiagnostics, Ack),
     (GetDefaultBranch, GetDefaultBranchResponse),
     (GetBlobContent, GetBlobContentResponse),
+    (GitLoadFileAtRevision, GitLoadFileAtRevisionResponse),
     (GetTreeDiff, GetTreeDiffResponse),
     (GitClone, GitCloneResponse),
     (ToggleLspLogs, Ack),
@@ -812,6 +815,7 @@ entity_messages!(
     GetDefaultBranch,
     GetTreeDiff,
     GetBlobContent,
+    GitLoadFileAtRevision,
     GitClone,
     GetAgentServerCommand,
     GetContextServerCommand,
@@ -1865,7 +1865,10 @@ fn build_command_posix(
     };
     write!(exec, "exec env ")?;
 
-    for (k, v) in input_env.iter() {
+    // Emit assignments in a stable order so the generated command is reproducible.
+    let mut input_env = input_env.iter().collect::<Vec<_>>();
+    input_env.sort_unstable_by_key(|(k, _)| *k);
+    for (k, v) in input_env {
         let assignment = format!("{k}={v}");
         let assignment = ssh_shell_kind
             .try_quote(&assignment)
@@ -2127,6 +2130,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_command_orders_env_assignments() -> Result<()> {
+        let build = |input_env: &HashMap<String, String>| {
+            build_command_posix(
+                Some("remote_program".to_string()),
+                &[],
+                input_env,
+                None,
+                None,
+                HashMap::default(),
+                PathStyle::Unix,
+                "/bin/bash",
+                ShellKind::Posix,
+                Vec::new(),
+                "user@host",
+                Interactive::No,
+            )
+        };
+
+        let mut input_env = HashMap::default();
+        for (k, v) in [
+            ("PATH", "$HOME/bin"),
+            ("HOME", "/home/user"),
+            ("EDITOR", "zed"),
+        ] {
+            input_env.insert(k.to_string(), v.to_string());
+        }
+        let mut reversed_env = HashMap::default();
+        for (k, v) in input_env.iter().rev() {
+            reversed_env.insert(k.clone(), v.clone());
+        }
+
+        let command = build(&input_env)?;
+        assert_eq!(
+            command.args.last().map(String::as_str),
+            Some("cd && exec env 'EDITOR=zed' 'HOME=/home/user' 'PATH=$HOME/bin' remote_program")
+        );
+        assert_eq!(command.args, build(&input_env)?.args);
+        assert_eq!(command.args, build(&reversed_env)?.args);
+
+        Ok(())
+    }
+
     #[test]
     fn test_build_command_quotes_env_assignment() -> Result<()> {
         let mut input_env = HashMap::default();
@@ -2159,6 +2205,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_command_quotes_working_directory() -> Result<()> {
+        let cd_command = |working_dir: &str| -> Result<String> {
+            let command = build_command_posix(
+                Some("remote_program".to_string()),
+                &[],
+                &HashMap::default(),
+                Some(working_dir.to_string()),
+                None,
+                HashMap::default(),
+                PathStyle::Unix,
+                "/bin/bash",
+                ShellKind::Posix,
+                vec![],
+                "user@host",
+                Interactive::No,
+            )?;
+            command
+                .args
+                .last()
+                .cloned()
+                .context("missing remote command argument")
+        };
+
+        assert_eq!(
+            cd_command("/srv/my project")?,
+            "cd '/srv/my project' && exec env remote_program"
+        );
+        assert_eq!(
+            cd_command("/srv/$HOME")?,
+            "cd '/srv/$HOME' && exec env remote_program"
+        );
+        assert_eq!(
+            cd_command("/srv/say \"hi\"")?,
+            "cd '/srv/say \"hi\"' && exec env remote_program"
+        );
+        assert_eq!(
+            cd_command("~/my dir")?,
+            "cd \"$HOME\"/'my dir' && exec env remote_program"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn scp_args_exclude_port_forward_flags() {
         let options = SshConnectionOptions {
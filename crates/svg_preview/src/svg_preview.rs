@@ -9,11 +9,25 @@ actions!(
     svg,
     [
         /// Opens a following SVG preview that syncs with the editor.
-        OpenFollowingPreview
+        OpenFollowingPreview,
+        /// Opens a following SVG preview to the side that outlines the element under the cursor.
+        OpenCaretFollowingPreview,
+        /// Zooms in the SVG preview.
+        SvgZoomIn,
+        /// Zooms out the SVG preview.
+        SvgZoomOut,
+        /// Rasterizes the SVG preview at a higher resolution.
+        SvgIncreaseRenderScale,
+        /// Rasterizes the SVG preview at a lower resolution.
+        SvgDecreaseRenderScale,
+        /// Exports the rendered SVG preview as a PNG file.
+        ExportPng
     ]
 );
 
 pub fn init(cx: &mut App) {
+    workspace::register_serializable_item::<svg_preview_view::SvgPreviewView>(cx);
+
     cx.observe_new(|workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -1,25 +1,55 @@
+use std::borrow::Cow;
 use std::mem;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::{Context as _, Result};
+use editor::{Editor, EditorEvent, MultiBufferOffset};
 use file_icons::FileIcons;
 use gpui::{
-    App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, ParentElement, Render,
-    RenderImage, Styled, Subscription, Task, WeakEntity, Window, div, img,
+    App, Bounds, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, ParentElement,
+    Render, RenderImage, SMOOTH_SVG_SCALE_FACTOR, Styled, Subscription, SvgRenderer, Task,
+    WeakEntity, Window, div, img, px,
 };
 use language::{Buffer, BufferEvent};
 use multi_buffer::MultiBuffer;
-use ui::prelude::*;
-use workspace::item::Item;
-use workspace::{Pane, Workspace};
+use project::{Project, ProjectPath};
+use ui::{Tooltip, prelude::*};
+use workspace::item::{Item, SerializableItem};
+use workspace::notifications::NotificationId;
+use workspace::{ItemId, Pane, Toast, Workspace, WorkspaceId, delete_unloaded_items};
 
-use crate::{OpenFollowingPreview, OpenPreview, OpenPreviewToTheSide};
+use crate::{
+    ExportPng, OpenCaretFollowingPreview, OpenFollowingPreview, OpenPreview, OpenPreviewToTheSide,
+    SvgDecreaseRenderScale, SvgIncreaseRenderScale, SvgZoomIn, SvgZoomOut,
+};
+
+const MIN_SCALE_FACTOR: f32 = 0.25;
+const MAX_SCALE_FACTOR: f32 = 20.0;
+const SCALE_FACTOR_STEP: f32 = 0.25;
+const RENDER_DEBOUNCE: Duration = Duration::from_millis(150);
 
 pub struct SvgPreviewView {
     focus_handle: FocusHandle,
+    mode: SvgPreviewMode,
     buffer: Option<Entity<Buffer>>,
-    current_svg: Option<Result<Arc<RenderImage>, SharedString>>,
+    /// The last successful render, kept on screen while the content fails to render.
+    current_svg: Option<Arc<RenderImage>>,
+    render_error: Option<SharedString>,
+    /// How large the preview is shown. Changing it only rescales the existing bitmap.
+    display_zoom: f32,
+    /// The scale the SVG is rasterized at. Changing it re-renders the SVG.
+    render_scale: f32,
+    /// Bounds of the element under the editor's caret, as a fraction of the SVG's size.
+    caret_highlight: Option<Bounds<f32>>,
+    /// The editor whose caret is outlined in `FollowCaret` mode.
+    followed_editor: Option<WeakEntity<Editor>>,
+    workspace: WeakEntity<Workspace>,
     _refresh: Task<()>,
+    _caret_refresh: Task<()>,
     _buffer_subscription: Option<Subscription>,
+    _editor_subscription: Option<Subscription>,
     _workspace_subscription: Option<Subscription>,
 }
 
@@ -29,6 +59,31 @@ pub enum SvgPreviewMode {
     Default,
     /// The preview will "follow" the last active editor of an SVG file.
     Follow,
+    /// Like `Follow`, and also outlines the element with an `id` that contains the
+    /// editor's caret.
+    FollowCaret,
+}
+
+impl SvgPreviewMode {
+    fn to_db(self) -> i64 {
+        match self {
+            Self::Default => 0,
+            Self::Follow => 1,
+            Self::FollowCaret => 2,
+        }
+    }
+
+    fn from_db(value: i64) -> Self {
+        match value {
+            1 => Self::Follow,
+            2 => Self::FollowCaret,
+            _ => Self::Default,
+        }
+    }
+
+    fn follows_active_editor(self) -> bool {
+        matches!(self, Self::Follow | Self::FollowCaret)
+    }
 }
 
 impl SvgPreviewView {
@@ -37,10 +92,10 @@ impl SvgPreviewView {
         active_buffer: Entity<MultiBuffer>,
         workspace_handle: WeakEntity<Workspace>,
         window: &mut Window,
-        cx: &mut Context<Workspace>,
+        cx: &mut App,
     ) -> Entity<Self> {
         cx.new(|cx| {
-            let workspace_subscription = if mode == SvgPreviewMode::Follow
+            let workspace_subscription = if mode.follows_active_editor()
                 && let Some(workspace) = workspace_handle.upgrade()
             {
                 Some(Self::subscribe_to_workspace(workspace, window, cx))
@@ -56,13 +111,30 @@ impl SvgPreviewView {
 
             let mut this = Self {
                 focus_handle: cx.focus_handle(),
+                mode,
                 buffer,
                 current_svg: None,
+                render_error: None,
+                display_zoom: 1.0,
+                render_scale: 1.0,
+                caret_highlight: None,
+                followed_editor: None,
+                workspace: workspace_handle.clone(),
                 _buffer_subscription: subscription,
+                _editor_subscription: None,
                 _workspace_subscription: workspace_subscription,
                 _refresh: Task::ready(()),
+                _caret_refresh: Task::ready(()),
             };
-            this.render_image(window, cx);
+            this.render_image(false, window, cx);
+            if mode == SvgPreviewMode::FollowCaret
+                && let Some(editor) = workspace_handle
+                    .upgrade()
+                    .and_then(|workspace| workspace.read(cx).active_item(cx))
+                    .and_then(|item| item.act_as::<Editor>(cx))
+            {
+                this.follow_caret(editor, window, cx);
+            }
 
             this
         })
@@ -78,59 +150,266 @@ impl SvgPreviewView {
             window,
             move |this: &mut SvgPreviewView, workspace, event: &workspace::Event, window, cx| {
                 if let workspace::Event::ActiveItemChanged = event {
-                    let workspace = workspace.read(cx);
-                    if let Some(active_item) = workspace.active_item(cx)
-                        && let Some(buffer) = active_item.downcast::<MultiBuffer>()
-                        && Self::is_svg_file(&buffer, cx)
-                    {
-                        let Some(buffer) = buffer.read(cx).as_singleton() else {
-                            return;
-                        };
-                        if this.buffer.as_ref() != Some(&buffer) {
-                            this._buffer_subscription =
-                                Some(Self::create_buffer_subscription(&buffer, window, cx));
-                            this.buffer = Some(buffer);
-                            this.render_image(window, cx);
-                            cx.notify();
-                        }
-                    } else {
+                    let Some(editor) = workspace
+                        .read(cx)
+                        .active_item(cx)
+                        .and_then(|item| item.act_as::<Editor>(cx))
+                        .filter(|editor| Self::is_svg_file(editor.read(cx).buffer(), cx))
+                    else {
+                        this.set_current(None, window, cx);
+                        return;
+                    };
+                    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                        return;
+                    };
+                    if this.buffer.as_ref() != Some(&buffer) {
+                        this._buffer_subscription =
+                            Some(Self::create_buffer_subscription(&buffer, window, cx));
+                        this.buffer = Some(buffer);
+                        // The last good render belongs to the previous file.
                         this.set_current(None, window, cx);
+                        cx.emit(());
+                        this.render_image(false, window, cx);
+                        cx.notify();
+                    }
+                    // The caret can only be located once the editor's file is previewed.
+                    if this.mode == SvgPreviewMode::FollowCaret {
+                        this.follow_caret(editor, window, cx);
                     }
                 }
             },
         )
     }
 
-    fn render_image(&mut self, window: &Window, cx: &mut Context<Self>) {
-        let Some(buffer) = self.buffer.as_ref() else {
+    /// Outlines the element under `editor`'s caret whenever its selections change.
+    fn follow_caret(&mut self, editor: Entity<Editor>, window: &Window, cx: &mut Context<Self>) {
+        let followed_editor = editor.downgrade();
+        if self.followed_editor.as_ref() != Some(&followed_editor) {
+            self._editor_subscription = Some(cx.subscribe_in(
+                &editor,
+                window,
+                |this, editor, event: &EditorEvent, _window, cx| {
+                    if let EditorEvent::SelectionsChanged { .. } = event {
+                        this.update_caret_highlight(editor, cx);
+                    }
+                },
+            ));
+            self.followed_editor = Some(followed_editor);
+        }
+        self.update_caret_highlight(&editor, cx);
+    }
+
+    fn update_caret_highlight(&mut self, editor: &Entity<Editor>, cx: &mut Context<Self>) {
+        let caret_offset = self.buffer.as_ref().and_then(|buffer| {
+            editor.update(cx, |editor, cx| Self::caret_offset(editor, buffer, cx))
+        });
+        let (Some(buffer), Some(caret_offset)) = (self.buffer.as_ref(), caret_offset) else {
+            self._caret_refresh = Task::ready(());
+            self.set_caret_highlight(None, cx);
             return;
         };
-        const SCALE_FACTOR: f32 = 1.0;
-
+        let text = buffer.read(cx).text();
         let renderer = cx.svg_renderer();
-        let content = buffer.read(cx).snapshot();
-        let background_task = cx.background_spawn(async move {
-            renderer.render_single_frame(content.text().as_bytes(), SCALE_FACTOR)
+
+        self._caret_refresh = cx.spawn(async move |this, cx| {
+            let bounds = cx
+                .background_spawn(async move {
+                    let id = element_id_at_offset(&text, caret_offset)?;
+                    renderer.element_bounds(text.as_bytes(), &id).ok().flatten()
+                })
+                .await;
+            this.update(cx, |this, cx| this.set_caret_highlight(bounds, cx))
+                .ok();
         });
+    }
+
+    /// Returns the caret's offset into `buffer`, if `editor` is editing it.
+    fn caret_offset(editor: &Editor, buffer: &Entity<Buffer>, cx: &mut App) -> Option<usize> {
+        let display_snapshot = editor.display_snapshot(cx);
+        let caret = editor
+            .selections
+            .last::<MultiBufferOffset>(&display_snapshot)
+            .head();
+        let (buffer_snapshot, buffer_offset) = display_snapshot
+            .buffer_snapshot()
+            .point_to_buffer_offset(caret)?;
+        (buffer_snapshot.remote_id() == buffer.read(cx).remote_id()).then_some(buffer_offset.0)
+    }
+
+    fn set_caret_highlight(
+        &mut self,
+        caret_highlight: Option<Bounds<f32>>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.caret_highlight != caret_highlight {
+            self.caret_highlight = caret_highlight;
+            cx.notify();
+        }
+    }
+
+    fn render_image(&mut self, wait_for_debounce: bool, window: &Window, cx: &mut Context<Self>) {
+        let Some(buffer) = self.buffer.clone() else {
+            return;
+        };
+        let render_scale = self.render_scale;
+        let renderer = cx.svg_renderer();
 
         self._refresh = cx.spawn_in(window, async move |this, cx| {
-            let result = background_task.await;
+            if wait_for_debounce {
+                // Replacing `_refresh` cancels the pending render, so a burst of edits
+                // only renders the content left once the user stops typing.
+                cx.background_executor().timer(RENDER_DEBOUNCE).await;
+            }
+
+            let content = buffer.read_with(cx, |buffer, _cx| buffer.snapshot());
+            let result = cx
+                .background_spawn(async move {
+                    render_svg(&renderer, content.text().as_bytes(), render_scale)
+                })
+                .await;
 
             this.update_in(cx, |view, window, cx| {
-                let current = result.map_err(|e| e.to_string().into());
-                view.set_current(Some(current), window, cx);
+                view.set_current(Some(result), window, cx);
             })
             .ok();
         });
     }
 
+    fn zoom_in(&mut self, _: &SvgZoomIn, _window: &mut Window, cx: &mut Context<Self>) {
+        self.set_display_zoom(self.display_zoom + SCALE_FACTOR_STEP, cx);
+    }
+
+    fn zoom_out(&mut self, _: &SvgZoomOut, _window: &mut Window, cx: &mut Context<Self>) {
+        self.set_display_zoom(self.display_zoom - SCALE_FACTOR_STEP, cx);
+    }
+
+    fn increase_render_scale(
+        &mut self,
+        _: &SvgIncreaseRenderScale,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_render_scale(self.render_scale + SCALE_FACTOR_STEP, window, cx);
+    }
+
+    fn decrease_render_scale(
+        &mut self,
+        _: &SvgDecreaseRenderScale,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_render_scale(self.render_scale - SCALE_FACTOR_STEP, window, cx);
+    }
+
+    pub fn set_display_zoom(&mut self, display_zoom: f32, cx: &mut Context<Self>) {
+        let display_zoom = clamp_scale_factor(display_zoom);
+        if display_zoom != self.display_zoom {
+            self.display_zoom = display_zoom;
+            cx.notify();
+        }
+    }
+
+    pub fn set_render_scale(&mut self, render_scale: f32, window: &Window, cx: &mut Context<Self>) {
+        let render_scale = clamp_scale_factor(render_scale);
+        if render_scale != self.render_scale {
+            self.render_scale = render_scale;
+            self.render_image(false, window, cx);
+        }
+    }
+
+    fn export_png(&mut self, _: &ExportPng, window: &mut Window, cx: &mut Context<Self>) {
+        if self.current_svg.is_none() {
+            self.show_export_toast("Nothing to export", cx);
+            return;
+        }
+
+        let suggested_name = self
+            .buffer
+            .as_ref()
+            .and_then(|buffer| buffer.read(cx).file())
+            .and_then(|file| {
+                std::path::Path::new(file.file_name(cx))
+                    .file_stem()
+                    .map(|stem| format!("{}.png", stem.to_string_lossy()))
+            })
+            .unwrap_or_else(|| "preview.png".to_string());
+        let path = cx.prompt_for_new_path(&PathBuf::default(), Some(&suggested_name));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            this.update(cx, |this, cx| this.export_current_frame(path, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Writes the currently rendered frame to `path` as a PNG, at the scale factor it was
+    /// rendered with.
+    pub fn export_current_frame(&self, path: PathBuf, cx: &mut Context<Self>) {
+        let Some(image) = self.current_svg.clone() else {
+            self.show_export_toast("Nothing to export", cx);
+            return;
+        };
+
+        let write_task = cx.background_spawn({
+            let path = path.clone();
+            async move { write_png(&image, &path) }
+        });
+        cx.spawn(async move |this, cx| {
+            let message = match write_task.await {
+                Ok(()) => format!("Exported SVG preview to {}", path.display()),
+                Err(error) => format!("Failed to export SVG preview: {error:#}"),
+            };
+            this.update(cx, |this, cx| this.show_export_toast(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn show_export_toast(&self, message: impl Into<Cow<'static, str>>, cx: &mut App) {
+        struct ExportPngToast;
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<ExportPngToast>(), message).autohide(),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    /// The message from the last failed render, if the current content failed to render.
+    pub fn last_render_error(&self) -> Option<&SharedString> {
+        self.render_error.as_ref()
+    }
+
+    /// Applies a render result. A failed render only records its error, so the last good
+    /// image stays visible; `None` clears both.
     fn set_current(
         &mut self,
-        image: Option<Result<Arc<RenderImage>, SharedString>>,
+        result: Option<Result<Arc<RenderImage>, SharedString>>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(Ok(image)) = mem::replace(&mut self.current_svg, image) {
+        let image = match result {
+            Some(Ok(image)) => {
+                self.render_error = None;
+                Some(image)
+            }
+            Some(Err(error)) => {
+                self.render_error = Some(error);
+                cx.notify();
+                return;
+            }
+            None => {
+                self.render_error = None;
+                None
+            }
+        };
+        if let Some(image) = mem::replace(&mut self.current_svg, image) {
             window.drop_image(image).ok();
         }
         cx.notify();
@@ -182,8 +461,11 @@ impl SvgPreviewView {
             buffer,
             window,
             move |this, _buffer, event: &BufferEvent, window, cx| match event {
-                BufferEvent::Edited { .. } | BufferEvent::Saved => {
-                    this.render_image(window, cx);
+                BufferEvent::Edited { .. } => {
+                    this.render_image(true, window, cx);
+                }
+                BufferEvent::Saved => {
+                    this.render_image(false, window, cx);
                 }
                 _ => {}
             },
@@ -273,37 +555,169 @@ impl SvgPreviewView {
                 cx.notify();
             }
         });
+
+        workspace.register_action(
+            move |workspace, _: &OpenCaretFollowingPreview, window, cx| {
+                if let Some(buffer) = Self::resolve_active_item_as_svg_buffer(workspace, cx) {
+                    let view = Self::create_svg_view(
+                        SvgPreviewMode::FollowCaret,
+                        workspace,
+                        buffer,
+                        window,
+                        cx,
+                    );
+                    let pane = workspace.active_pane().clone();
+                    let target_pane = workspace.adjacent_pane_of(&pane, window, cx);
+                    target_pane.update(cx, |pane, cx| {
+                        pane.add_item(Box::new(view), false, false, None, window, cx)
+                    });
+                    cx.notify();
+                }
+            },
+        );
     }
 }
 
 impl Render for SvgPreviewView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let render_state = (self.current_svg.clone(), self.render_error.clone());
         v_flex()
             .id("SvgPreview")
             .key_context("SvgPreview")
             .track_focus(&self.focus_handle(cx))
+            .on_action(cx.listener(Self::zoom_in))
+            .on_action(cx.listener(Self::zoom_out))
+            .on_action(cx.listener(Self::increase_render_scale))
+            .on_action(cx.listener(Self::decrease_render_scale))
+            .on_action(cx.listener(Self::export_png))
             .size_full()
             .bg(cx.theme().colors().editor_background)
             .flex()
             .justify_center()
             .items_center()
-            .map(|this| match self.current_svg.clone() {
-                Some(Ok(image)) => {
-                    this.child(img(image).max_w_full().max_h_full().with_fallback(|| {
-                        h_flex()
-                            .p_4()
-                            .gap_2()
-                            .child(Icon::new(IconName::Warning))
-                            .child("Failed to load SVG image")
-                            .into_any_element()
-                    }))
+            .map(|this| match render_state {
+                (Some(image), render_error) => {
+                    // The bitmap was rasterized at `render_scale`, so undo that before applying
+                    // the display zoom.
+                    let size = image.size(0);
+                    let scale = self.display_zoom / (self.render_scale * SMOOTH_SVG_SCALE_FACTOR);
+                    let width = px(size.width.0 as f32 * scale);
+                    let height = px(size.height.0 as f32 * scale);
+                    this.when_some(render_error, |this, error| {
+                        this.child(
+                            h_flex()
+                                .gap_1()
+                                .id("svg-preview-stale-render")
+                                .tooltip(Tooltip::text(error))
+                                .child(
+                                    Icon::new(IconName::Warning)
+                                        .size(IconSize::Small)
+                                        .color(Color::Warning),
+                                )
+                                .child(
+                                    Label::new("Invalid SVG, showing last good render")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                    })
+                    .child(
+                        div()
+                            .relative()
+                            .w(width)
+                            .h(height)
+                            .max_w_full()
+                            .max_h_full()
+                            .child(img(image).size_full().with_fallback(|| {
+                                h_flex()
+                                    .p_4()
+                                    .gap_2()
+                                    .child(Icon::new(IconName::Warning))
+                                    .child("Failed to load SVG image")
+                                    .into_any_element()
+                            }))
+                            .when_some(self.caret_highlight, |this, bounds| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .left(relative(bounds.origin.x))
+                                        .top(relative(bounds.origin.y))
+                                        .w(relative(bounds.size.width))
+                                        .h(relative(bounds.size.height))
+                                        .border_1()
+                                        .border_color(cx.theme().colors().border_focused),
+                                )
+                            }),
+                    )
                 }
-                Some(Err(e)) => this.child(div().p_4().child(e).into_any_element()),
-                None => this.child(div().p_4().child("No SVG file selected")),
+                (None, Some(error)) => this.child(
+                    v_flex()
+                        .p_4()
+                        .gap_2()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Icon::new(IconName::Warning).color(Color::Warning))
+                                .child(Label::new("Failed to render SVG")),
+                        )
+                        .child(Label::new(error).color(Color::Muted)),
+                ),
+                (None, None) => this.child(div().p_4().child("No SVG file selected")),
             })
     }
 }
 
+/// Returns the `id` of the innermost element with one whose source contains `offset`.
+fn element_id_at_offset(source: &str, offset: usize) -> Option<String> {
+    let document = roxmltree::Document::parse_with_options(
+        source,
+        roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..roxmltree::ParsingOptions::default()
+        },
+    )
+    .ok()?;
+    document
+        .descendants()
+        .filter(|node| node.is_element() && node.range().contains(&offset))
+        .filter_map(|node| node.attribute("id"))
+        .last()
+        .map(str::to_string)
+}
+
+fn clamp_scale_factor(scale_factor: f32) -> f32 {
+    scale_factor.clamp(MIN_SCALE_FACTOR, MAX_SCALE_FACTOR)
+}
+
+/// Renders `bytes` as an SVG, keeping the parser's message (which includes the line and
+/// column of malformed markup) so the preview can show it.
+fn render_svg(
+    renderer: &SvgRenderer,
+    bytes: &[u8],
+    scale_factor: f32,
+) -> Result<Arc<RenderImage>, SharedString> {
+    renderer
+        .render_single_frame(bytes, scale_factor)
+        .map_err(|error| error.to_string().into())
+}
+
+fn write_png(image: &RenderImage, path: &std::path::Path) -> Result<()> {
+    let size = image.size(0);
+    let mut pixels = image
+        .as_bytes(0)
+        .context("rendered SVG has no frames")?
+        .to_vec();
+    // Rendered images are stored as BGRA, PNG expects RGBA.
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    let buffer = image::RgbaImage::from_raw(size.width.0 as u32, size.height.0 as u32, pixels)
+        .context("rendered SVG frame has an unexpected size")?;
+    buffer
+        .save_with_format(path, image::ImageFormat::Png)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
 impl Focusable for SvgPreviewView {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -338,3 +752,597 @@ impl Item for SvgPreviewView {
 
     fn to_item_events(_event: &Self::Event, _f: &mut dyn FnMut(workspace::item::ItemEvent)) {}
 }
+
+impl SerializableItem for SvgPreviewView {
+    fn serialized_item_kind() -> &'static str {
+        "SvgPreviewView"
+    }
+
+    fn deserialize(
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        workspace_id: WorkspaceId,
+        item_id: ItemId,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let db = persistence::SvgPreviewDb::global(cx);
+        window.spawn(cx, async move |cx| {
+            let (abs_path, mode_value) = db
+                .get_preview(item_id, workspace_id)?
+                .context("No SVG preview entry found")?;
+            let mode = SvgPreviewMode::from_db(mode_value);
+
+            let (worktree, relative_path) = project
+                .update(cx, |project, cx| {
+                    project.find_or_create_worktree(abs_path.clone(), false, cx)
+                })
+                .await
+                .context("Path not found")?;
+            let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+
+            let project_path = ProjectPath {
+                worktree_id,
+                path: relative_path,
+            };
+
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                .await?;
+            // Opening a path that was deleted yields an empty buffer rather than an error,
+            // which would restore a preview of nothing.
+            anyhow::ensure!(
+                buffer.read_with(cx, |buffer, _| {
+                    buffer.file().is_some_and(|file| file.disk_state().exists())
+                }),
+                "SVG file {} no longer exists",
+                abs_path.display()
+            );
+
+            cx.update(|window, cx| {
+                let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+                SvgPreviewView::new(mode, buffer, workspace, window, cx)
+            })
+        })
+    }
+
+    fn cleanup(
+        workspace_id: WorkspaceId,
+        alive_items: Vec<ItemId>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let db = persistence::SvgPreviewDb::global(cx);
+        delete_unloaded_items(alive_items, workspace_id, "svg_previews", &db, cx)
+    }
+
+    fn serialize(
+        &mut self,
+        workspace: &mut Workspace,
+        item_id: ItemId,
+        _closing: bool,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let workspace_id = workspace.database_id()?;
+        let file = self.buffer.as_ref()?.read(cx).file()?;
+        let worktree_id = file.worktree_id(cx);
+        let abs_path = workspace
+            .project()
+            .read(cx)
+            .worktree_for_id(worktree_id, cx)?
+            .read(cx)
+            .absolutize(file.path());
+        let mode = self.mode.to_db();
+        let db = persistence::SvgPreviewDb::global(cx);
+        Some(cx.background_spawn(async move {
+            db.save_preview(item_id, workspace_id, abs_path, mode).await
+        }))
+    }
+
+    fn should_serialize(&self, _event: &Self::Event) -> bool {
+        true
+    }
+}
+
+mod persistence {
+    use std::path::PathBuf;
+
+    use db::{
+        query,
+        sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
+        sqlez_macros::sql,
+    };
+    use workspace::{ItemId, WorkspaceDb, WorkspaceId};
+
+    pub struct SvgPreviewDb(ThreadSafeConnection);
+
+    impl Domain for SvgPreviewDb {
+        const NAME: &str = stringify!(SvgPreviewDb);
+
+        const MIGRATIONS: &[&str] = &[sql!(
+            CREATE TABLE svg_previews (
+                workspace_id INTEGER,
+                item_id INTEGER,
+                abs_path BLOB,
+                mode INTEGER NOT NULL DEFAULT 0,
+
+                PRIMARY KEY(workspace_id, item_id),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+    }
+
+    db::static_connection!(SvgPreviewDb, [WorkspaceDb]);
+
+    impl SvgPreviewDb {
+        query! {
+            pub async fn save_preview(
+                item_id: ItemId,
+                workspace_id: WorkspaceId,
+                abs_path: PathBuf,
+                mode: i64
+            ) -> Result<()> {
+                INSERT OR REPLACE INTO svg_previews(item_id, workspace_id, abs_path, mode)
+                VALUES (?, ?, ?, ?)
+            }
+        }
+
+        query! {
+            pub fn get_preview(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<(PathBuf, i64)>> {
+                SELECT abs_path, mode
+                FROM svg_previews
+                WHERE item_id = ? AND workspace_id = ?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::path::{Path, PathBuf};
+    use std::rc::Rc;
+
+    use editor::SelectionEffects;
+    use gpui::{TestAppContext, VisualTestContext, point, size};
+    use serde_json::json;
+    use util::path;
+    use workspace::{AppState, MultiWorkspace, open_paths};
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_preview_is_restored_for_the_serialized_file(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/dir"),
+                json!({
+                    "icon.svg": "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"4\" height=\"4\"/>",
+                }),
+            )
+            .await;
+
+        cx.update(|cx| {
+            open_paths(
+                &[PathBuf::from(path!("/dir"))],
+                app_state.clone(),
+                workspace::OpenOptions::default(),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+
+        let multi_workspace = cx.update(|cx| cx.windows()[0].downcast::<MultiWorkspace>().unwrap());
+        let workspace = multi_workspace
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let project = workspace.read_with(cx, |workspace, _| workspace.project().clone());
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/icon.svg"), cx)
+            })
+            .await
+            .unwrap();
+
+        let (preview, workspace_id) = multi_workspace
+            .update(cx, |_, window, cx| {
+                workspace.update(cx, |workspace, cx| {
+                    workspace.set_random_database_id();
+                    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+                    let preview = SvgPreviewView::new(
+                        SvgPreviewMode::Follow,
+                        buffer,
+                        workspace.weak_handle(),
+                        window,
+                        cx,
+                    );
+                    workspace.active_pane().update(cx, |pane, cx| {
+                        pane.add_item(Box::new(preview.clone()), true, true, None, window, cx)
+                    });
+                    (preview, workspace.database_id().unwrap())
+                })
+            })
+            .unwrap();
+        let workspace_serialization_tasks = multi_workspace
+            .update(cx, |multi_workspace, window, cx| {
+                multi_workspace.flush_all_serialization(window, cx)
+            })
+            .unwrap();
+        for task in workspace_serialization_tasks {
+            task.await;
+        }
+
+        let item_id = preview.entity_id().as_u64();
+        let serialize_task = multi_workspace
+            .update(cx, |_, window, cx| {
+                workspace.update(cx, |workspace, cx| {
+                    preview
+                        .update(cx, |preview, cx| {
+                            preview.serialize(workspace, item_id, false, window, cx)
+                        })
+                        .unwrap()
+                })
+            })
+            .unwrap();
+        serialize_task.await.unwrap();
+
+        let deserialize_task = multi_workspace
+            .update(cx, |_, window, cx| {
+                SvgPreviewView::deserialize(
+                    project.clone(),
+                    workspace.downgrade(),
+                    workspace_id,
+                    item_id,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap();
+        let restored = deserialize_task.await.unwrap();
+        restored.read_with(cx, |restored, cx| {
+            assert_eq!(restored.mode, SvgPreviewMode::Follow);
+            let file = restored.buffer.as_ref().unwrap().read(cx).file().unwrap();
+            assert_eq!(file.path().as_unix_str(), "icon.svg");
+        });
+
+        cx.update(|cx| persistence::SvgPreviewDb::global(cx))
+            .save_preview(
+                item_id,
+                workspace_id,
+                PathBuf::from(path!("/dir/missing.svg")),
+                SvgPreviewMode::Default.to_db(),
+            )
+            .await
+            .unwrap();
+        let deserialize_task = multi_workspace
+            .update(cx, |_, window, cx| {
+                SvgPreviewView::deserialize(
+                    project.clone(),
+                    workspace.downgrade(),
+                    workspace_id,
+                    item_id,
+                    window,
+                    cx,
+                )
+            })
+            .unwrap();
+        assert!(deserialize_task.await.is_err());
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            editor::init(cx);
+            crate::init(cx);
+            state
+        })
+    }
+
+    #[gpui::test]
+    fn test_malformed_svg_reports_render_error(cx: &mut TestAppContext) {
+        let renderer = cx.update(|cx| cx.svg_renderer());
+        let error = render_svg(
+            &renderer,
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"><g></svg>",
+            1.0,
+        )
+        .expect_err("malformed SVG should fail to render");
+        assert!(!error.is_empty());
+    }
+
+    const VALID_SVG: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"4\" height=\"4\"/>";
+
+    fn build_preview(
+        text: &str,
+        cx: &mut VisualTestContext,
+    ) -> (Entity<SvgPreviewView>, Entity<Buffer>) {
+        cx.update(|window, cx| {
+            let buffer = cx.new(|cx| Buffer::local(text, cx));
+            let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+            let preview = SvgPreviewView::new(
+                SvgPreviewMode::Default,
+                multi_buffer,
+                WeakEntity::new_invalid(),
+                window,
+                cx,
+            );
+            (preview, buffer)
+        })
+    }
+
+    fn rendered_image(
+        preview: &Entity<SvgPreviewView>,
+        cx: &mut VisualTestContext,
+    ) -> Arc<RenderImage> {
+        preview.read_with(cx, |preview, _| {
+            preview
+                .current_svg
+                .clone()
+                .expect("expected a rendered image")
+        })
+    }
+
+    #[gpui::test]
+    fn test_display_zoom_does_not_rerender(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let (preview, _buffer) = build_preview(VALID_SVG, cx);
+        cx.run_until_parked();
+        let initial_image = rendered_image(&preview, cx);
+
+        preview.update(cx, |preview, cx| preview.set_display_zoom(2.0, cx));
+        cx.run_until_parked();
+        assert!(Arc::ptr_eq(&rendered_image(&preview, cx), &initial_image));
+
+        cx.update(|window, cx| {
+            preview.update(cx, |preview, cx| preview.set_render_scale(2.0, window, cx))
+        });
+        cx.run_until_parked();
+        let rerendered_image = rendered_image(&preview, cx);
+        assert!(!Arc::ptr_eq(&rerendered_image, &initial_image));
+        assert_eq!(
+            rerendered_image.size(0).width.0,
+            initial_image.size(0).width.0 * 2
+        );
+    }
+
+    #[gpui::test]
+    fn test_rapid_edits_render_once_after_debounce(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let (preview, buffer) = build_preview(VALID_SVG, cx);
+        cx.run_until_parked();
+        let initial_image = rendered_image(&preview, cx);
+
+        let renders = Rc::new(Cell::new(0));
+        let _subscription = cx.update(|_, cx| {
+            let renders = renders.clone();
+            cx.observe(&preview, move |_, _| renders.set(renders.get() + 1))
+        });
+
+        for size in [8, 12, 16] {
+            buffer.update(cx, |buffer, cx| {
+                buffer.set_text(
+                    format!(
+                        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\"/>"
+                    ),
+                    cx,
+                )
+            });
+            cx.executor().advance_clock(RENDER_DEBOUNCE / 2);
+            cx.run_until_parked();
+        }
+        assert_eq!(renders.get(), 0);
+        assert!(Arc::ptr_eq(&rendered_image(&preview, cx), &initial_image));
+
+        cx.executor().advance_clock(RENDER_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(renders.get(), 1);
+        assert_eq!(
+            rendered_image(&preview, cx).size(0).width.0,
+            initial_image.size(0).width.0 * 4
+        );
+    }
+
+    #[gpui::test]
+    fn test_failed_render_keeps_last_good_render(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let (preview, buffer) = build_preview(VALID_SVG, cx);
+        cx.run_until_parked();
+        let good_image = rendered_image(&preview, cx);
+
+        let set_text = |text: &str, cx: &mut VisualTestContext| {
+            buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+            cx.executor().advance_clock(RENDER_DEBOUNCE);
+            cx.run_until_parked();
+        };
+
+        set_text("<svg xmlns=\"http://www.w3.org/2000/svg\"><g></svg>", cx);
+        assert!(Arc::ptr_eq(&rendered_image(&preview, cx), &good_image));
+        assert!(preview.read_with(cx, |preview, _| preview.last_render_error().is_some()));
+
+        set_text(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"8\" height=\"8\"/>",
+            cx,
+        );
+        assert!(!Arc::ptr_eq(&rendered_image(&preview, cx), &good_image));
+        assert!(preview.read_with(cx, |preview, _| preview.last_render_error().is_none()));
+    }
+
+    #[gpui::test]
+    fn test_follow_caret_outlines_element_under_caret(cx: &mut TestAppContext) {
+        init_test(cx);
+        let cx = cx.add_empty_window();
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect id="left" width="10" height="10"/><rect id="right" x="10" width="10" height="5"/></svg>"#;
+        let (preview, editor) = cx.update(|window, cx| {
+            let buffer = cx.new(|cx| Buffer::local(source, cx));
+            let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+            let editor =
+                cx.new(|cx| Editor::for_multibuffer(multi_buffer.clone(), None, window, cx));
+            let preview = SvgPreviewView::new(
+                SvgPreviewMode::FollowCaret,
+                multi_buffer,
+                WeakEntity::new_invalid(),
+                window,
+                cx,
+            );
+            preview.update(cx, |preview, cx| {
+                preview.follow_caret(editor.clone(), window, cx)
+            });
+            (preview, editor)
+        });
+
+        let move_caret = |offset: usize, cx: &mut VisualTestContext| {
+            editor.update_in(cx, |editor, window, cx| {
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_ranges([MultiBufferOffset(offset)..MultiBufferOffset(offset)])
+                });
+            });
+            cx.run_until_parked();
+            preview.read_with(cx, |preview, _| preview.caret_highlight)
+        };
+
+        assert_eq!(
+            move_caret(source.find("right").unwrap(), cx),
+            Some(Bounds::new(point(0.5, 0.0), size(0.5, 0.5)))
+        );
+        assert_eq!(
+            move_caret(source.find("left").unwrap(), cx),
+            Some(Bounds::new(point(0.0, 0.0), size(0.5, 1.0)))
+        );
+        // The root element has no `id`.
+        assert_eq!(move_caret(0, cx), None);
+    }
+
+    #[gpui::test]
+    async fn test_follow_caret_outlines_element_in_newly_active_editor(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let left_source = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect id="left" width="10" height="10"/></svg>"#;
+        let right_source = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect id="right" x="10" width="10" height="5"/></svg>"#;
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/dir"),
+                json!({ "left.svg": left_source, "right.svg": right_source }),
+            )
+            .await;
+        let project = Project::test(app_state.fs.clone(), [Path::new(path!("/dir"))], cx).await;
+        let open_buffer = |path: &'static str, cx: &mut TestAppContext| {
+            project.update(cx, |project, cx| project.open_local_buffer(path, cx))
+        };
+        let left_buffer = open_buffer(path!("/dir/left.svg"), cx).await.unwrap();
+        let right_buffer = open_buffer(path!("/dir/right.svg"), cx).await.unwrap();
+
+        let multi_workspace =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(multi_workspace.into(), cx);
+
+        let add_editor = |buffer: Entity<Buffer>, caret: usize, cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::for_buffer(buffer, Some(project.clone()), window, cx);
+                    editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                        s.select_ranges([MultiBufferOffset(caret)..MultiBufferOffset(caret)])
+                    });
+                    editor
+                });
+                workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+                editor
+            })
+        };
+        let right_editor = add_editor(right_buffer, right_source.find("right").unwrap(), cx);
+        let left_editor = add_editor(left_buffer, left_source.find("left").unwrap(), cx);
+
+        let preview = workspace.update_in(cx, |workspace, window, cx| {
+            SvgPreviewView::new(
+                SvgPreviewMode::FollowCaret,
+                left_editor.read(cx).buffer().clone(),
+                workspace.weak_handle(),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        preview.read_with(cx, |preview, _| {
+            assert_eq!(
+                preview.caret_highlight,
+                Some(Bounds::new(point(0.0, 0.0), size(0.5, 1.0)))
+            );
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.activate_item(&right_editor, true, true, window, cx);
+        });
+        cx.run_until_parked();
+        preview.read_with(cx, |preview, cx| {
+            assert_eq!(
+                preview.buffer,
+                right_editor.read(cx).buffer().read(cx).as_singleton()
+            );
+            assert_eq!(
+                preview.caret_highlight,
+                Some(Bounds::new(point(0.5, 0.0), size(0.5, 0.5)))
+            );
+        });
+    }
+
+    #[test]
+    fn test_element_id_at_offset_finds_innermost_element_with_id() {
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg"><g id="outer"><rect/><circle id="inner" r="1"/></g><path d=""/></svg>"#;
+        assert_eq!(
+            element_id_at_offset(source, source.find("<rect").unwrap()).as_deref(),
+            Some("outer")
+        );
+        assert_eq!(
+            element_id_at_offset(source, source.find("r=\"1\"").unwrap()).as_deref(),
+            Some("inner")
+        );
+        assert_eq!(
+            element_id_at_offset(source, source.find("<path").unwrap()),
+            None
+        );
+        // Malformed markup has no elements to locate.
+        assert_eq!(element_id_at_offset("<svg><g id=", 5), None);
+    }
+
+    #[gpui::test]
+    fn test_zoom_in_is_clamped_to_max_scale_factor(cx: &mut TestAppContext) {
+        struct PreviewRoot(Entity<SvgPreviewView>);
+
+        impl Render for PreviewRoot {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                self.0.clone()
+            }
+        }
+
+        init_test(cx);
+        let cx = cx.add_empty_window();
+        let (preview, _buffer) = build_preview(VALID_SVG, cx);
+        cx.update(|window, cx| {
+            window.replace_root(cx, |_, _| PreviewRoot(preview.clone()));
+            let focus_handle = preview.read(cx).focus_handle(cx);
+            window.focus(&focus_handle, cx);
+        });
+        cx.run_until_parked();
+        let display_zoom =
+            |cx: &mut VisualTestContext| preview.read_with(cx, |preview, _| preview.display_zoom);
+
+        for _ in 0..200 {
+            cx.dispatch_action(SvgZoomIn);
+        }
+        assert_eq!(display_zoom(cx), MAX_SCALE_FACTOR);
+
+        for _ in 0..200 {
+            cx.dispatch_action(SvgZoomOut);
+        }
+        assert_eq!(display_zoom(cx), MIN_SCALE_FACTOR);
+    }
+}
@@ -2165,7 +2165,7 @@ fn first_project_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
 mod tests {
     use super::*;
     use gpui::{TestAppContext, VisualTestContext};
-    use project::{Entry, Project, ProjectPath, Worktree};
+    use project::{Entry, Project, ProjectPath, Worktree, terminals::TerminalOptions};
     use remote::RemoteClient;
     use std::path::{Path, PathBuf};
     use util::paths::PathStyle;
@@ -2915,6 +2915,161 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_project_title_labels_shell_and_task_terminals(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let (project, _workspace) = init_test(cx).await;
+
+        let shell = project
+            .update(cx, |project, cx| {
+                project.create_terminal_shell_with_options(
+                    None,
+                    TerminalOptions {
+                        title: Some("Dev server".into()),
+                        ..TerminalOptions::default()
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        shell.read_with(cx, |terminal, _| {
+            assert_eq!(terminal.title(false), "Dev server");
+        });
+
+        let task = project
+            .update(cx, |project, cx| {
+                project.create_terminal_task_with_options(
+                    task::SpawnInTerminal {
+                        label: "cargo build".to_string(),
+                        full_label: "cargo build".to_string(),
+                        command: Some("echo".to_string()),
+                        ..task::SpawnInTerminal::default()
+                    },
+                    TerminalOptions {
+                        title: Some("Build".into()),
+                        ..TerminalOptions::default()
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        task.read_with(cx, |terminal, _| {
+            assert_eq!(terminal.title(false), "Build");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_terminals_can_skip_cli_environment(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let (project, _workspace) = init_test(cx).await;
+        project.update(cx, |project, cx| {
+            project.environment().update(cx, |environment, _| {
+                environment.set_cli_environment_for_testing(collections::HashMap::from_iter([(
+                    "FROM_CLI".to_string(),
+                    "1".to_string(),
+                )]))
+            })
+        });
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.terminal.get_or_insert_default().project.env =
+                        Some(collections::HashMap::from_iter([(
+                            "FROM_SETTINGS".to_string(),
+                            "1".to_string(),
+                        )]));
+                });
+            });
+        });
+
+        let cwd = std::env::temp_dir();
+        for inherit_cli_env in [true, false] {
+            let shell = project
+                .update(cx, |project, cx| {
+                    project.create_terminal_shell_with_options(
+                        Some(cwd.clone()),
+                        TerminalOptions {
+                            inherit_cli_env,
+                            ..TerminalOptions::default()
+                        },
+                        cx,
+                    )
+                })
+                .await
+                .unwrap();
+            let task = project
+                .update(cx, |project, cx| {
+                    project.create_terminal_task_with_options(
+                        task::SpawnInTerminal {
+                            command: Some("echo".to_string()),
+                            cwd: Some(cwd.clone()),
+                            env: collections::HashMap::from_iter([(
+                                "FROM_TASK".to_string(),
+                                "1".to_string(),
+                            )]),
+                            ..task::SpawnInTerminal::default()
+                        },
+                        TerminalOptions {
+                            inherit_cli_env,
+                            ..TerminalOptions::default()
+                        },
+                        cx,
+                    )
+                })
+                .await
+                .unwrap();
+
+            for terminal in [&shell, &task] {
+                terminal.read_with(cx, |terminal, _| {
+                    let env = terminal.env();
+                    assert_eq!(
+                        env.contains_key("FROM_CLI"),
+                        inherit_cli_env,
+                        "inherit_cli_env: {inherit_cli_env}, env: {env:?}"
+                    );
+                    assert!(env.contains_key("FROM_SETTINGS"), "env: {env:?}");
+                });
+            }
+            task.read_with(cx, |terminal, _| {
+                assert!(terminal.env().contains_key("FROM_TASK"));
+            });
+        }
+    }
+
+    #[gpui::test]
+    async fn test_task_spawn_failure_names_command_and_cwd(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let (project, _workspace) = init_test(cx).await;
+
+        let missing_cwd = std::env::temp_dir().join("zed-missing-task-cwd");
+        let error = project
+            .update(cx, |project, cx| {
+                project.create_terminal_task(
+                    task::SpawnInTerminal {
+                        command: Some("cargo".to_string()),
+                        cwd: Some(missing_cwd.clone()),
+                        ..task::SpawnInTerminal::default()
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap_err();
+
+        let message = format!("{error:#}");
+        assert!(message.contains("local terminal"), "{message}");
+        assert!(message.contains("`cargo`"), "{message}");
+        assert!(
+            message.contains(&missing_cwd.display().to_string()),
+            "{message}"
+        );
+    }
+
     #[gpui::test]
     async fn test_set_custom_title(cx: &mut TestAppContext) {
         cx.executor().allow_parking();